@@ -0,0 +1,43 @@
+//! measures the algorithm on complete graphs of different sizes and on a long chain
+//! every benchmark is run multiple times with fixed seeds so the numbers are comparable between versions
+//! run with `cargo bench --bench coloring`
+
+use std::time::{Duration, Instant};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rs_graph::VecGraph;
+
+use color_reduction::{AlgorithmOptions, distributed_randomized_coloring_algorithm, Node};
+use color_reduction::generators::{chain, complete_graph};
+
+const RUNS: u64 = 10;
+
+/// runs the algorithm `RUNS` times on the graph and prints the fastest and average time
+fn bench(name: &str, (graph, nodes, delta): (VecGraph, Vec<Node>, usize)) {
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+
+    for seed in 0..RUNS {
+        let mut nodes = nodes.clone();
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let start = Instant::now();
+        distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut rng)
+            .expect("algorithm did not converge");
+        let elapsed = start.elapsed();
+
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+
+    println!("{name:24} fastest {:>12?}  average {:>12?}", fastest, total / RUNS as u32);
+}
+
+fn main() {
+    bench("complete graph 50", complete_graph(50));
+    bench("complete graph 200", complete_graph(200));
+    bench("complete graph 1000", complete_graph(1000));
+    bench("complete graph 2000", complete_graph(2000));
+    bench("chain 10000", chain(10000));
+}
@@ -0,0 +1,35 @@
+//! measures the algorithm on a complete graph with 2000 nodes
+//! run `cargo bench --bench parallel` and `cargo bench --bench parallel --features parallel` to compare
+//! the serial and the parallel version
+
+use std::time::{Duration, Instant};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use color_reduction::{AlgorithmOptions, distributed_randomized_coloring_algorithm};
+use color_reduction::generators::complete_graph;
+
+const NUM_NODES: usize = 2000;
+const RUNS: u64 = 5;
+
+fn main() {
+    let version = if cfg!(feature = "parallel") { "parallel" } else { "serial" };
+    let (graph, nodes, delta) = complete_graph(NUM_NODES);
+    let mut total = Duration::ZERO;
+
+    for seed in 0..RUNS {
+        let mut nodes = nodes.clone();
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let start = Instant::now();
+        let stats = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut rng)
+            .expect("algorithm did not converge");
+        let elapsed = start.elapsed();
+        total += elapsed;
+
+        println!("{version}: seed {seed} took {:?} for {} rounds", elapsed, stats.rounds);
+    }
+
+    println!("{version}: complete graph with {NUM_NODES} nodes took {:?} on average", total / RUNS as u32);
+}
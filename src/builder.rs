@@ -0,0 +1,64 @@
+use rs_graph::{Builder, VecGraph};
+use rs_graph::vecgraph::{self, VecGraphBuilder};
+
+use crate::Node;
+
+/// builds an undirected graph together with the nodes the algorithm colors and its max degree
+/// vertices are numbered from 0 in the order they are added, vertex `i` belongs to the node with id `i`
+/// like in the generators each undirected edge has to be added only once
+pub struct GraphBuilder {
+    builder: VecGraphBuilder<u32>,
+    vertices: Vec<vecgraph::Node<u32>>,
+    degrees: Vec<usize>,
+    delta: usize,
+}
+
+impl GraphBuilder {
+    /// creates a builder for a graph with `num_nodes` vertices and no edges
+    pub fn new(num_nodes: usize) -> GraphBuilder {
+        let mut builder = VecGraphBuilder::new();
+        let vertices = builder.add_nodes(num_nodes);
+
+        GraphBuilder {
+            builder,
+            vertices,
+            degrees: vec![0; num_nodes],
+            delta: 0,
+        }
+    }
+
+    /// adds a vertex without any edges and returns its index
+    pub fn add_node(&mut self) -> usize {
+        self.vertices.push(self.builder.add_node());
+        self.degrees.push(0);
+        self.vertices.len() - 1
+    }
+
+    /// adds an undirected edge between the vertices `u` and `v`
+    /// panics on a self-loop since such a vertex could never be colored
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert_ne!(u, v, "self-loops are not allowed, a vertex can't be colored differently from itself");
+        self.builder.add_edge(self.vertices[u], self.vertices[v]);
+
+        for w in [u, v] {
+            self.degrees[w] += 1;
+            self.delta = self.delta.max(self.degrees[w]);
+        }
+    }
+
+    /// returns the number of vertices added so far
+    pub fn num_nodes(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// returns the max degree of the edges added so far
+    pub fn delta(&self) -> usize {
+        self.delta
+    }
+
+    /// returns the graph, a vector of nodes and delta (max degree)
+    pub fn build(self) -> (VecGraph, Vec<Node>, usize) {
+        let nodes = (0..self.vertices.len()).map(Node::new).collect();
+        (self.builder.into_graph(), nodes, self.delta)
+    }
+}
@@ -0,0 +1,569 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+use rs_graph::{Builder, VecGraph};
+use rs_graph::traits::{FiniteGraph, Indexable};
+use rs_graph::vecgraph::VecGraphBuilder;
+
+use crate::input::graph_from_edges;
+use crate::Node;
+
+#[allow(non_snake_case)]
+fn N(id: usize) -> Node {
+    Node::new(id)
+}
+
+/// creates a complete graph with `num_nodes` vertices
+/// the graph has max degree `num_nodes - 1`
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn complete_graph(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
+    let mut nodes = Vec::with_capacity(num_nodes);
+    let mut g = VecGraphBuilder::new();
+    let g_nodes = g.add_nodes(num_nodes);
+
+    for n1 in &g_nodes {
+        for n2 in &g_nodes {
+            if n1.index() < n2.index() {
+                g.add_edge(*n1, *n2);
+            }
+        }
+        nodes.push(N(n1.index()));
+    }
+
+    // without any vertices there is no degree at all, so delta is 0 too
+    let delta = num_nodes.saturating_sub(1);
+    (g.into_graph(), nodes, delta)
+}
+
+/// creates a graph that is a chain of vertices with `num_nodes` vertices
+/// the graph has max degree 2
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn chain(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
+    let mut nodes = Vec::with_capacity(num_nodes);
+    let mut g = VecGraphBuilder::new();
+
+    let g_nodes = g.add_nodes(num_nodes);
+
+    for n in &g_nodes {
+        nodes.push(N(n.index()));
+    }
+
+    for i in 1..g_nodes.len() {
+        g.add_edge(g_nodes[i - 1], g_nodes[i]);
+    }
+
+    (g.into_graph(), nodes, num_nodes.saturating_sub(1).min(2))
+}
+
+/// creates a graph that is a cycle of vertices with `num_nodes` vertices
+/// it's a chain where the last vertex is also connected to the first one
+/// with less than 3 vertices there is no cycle, so it's the same as a chain
+/// the graph has max degree 2
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn cycle(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
+    let mut nodes = Vec::with_capacity(num_nodes);
+    let mut g = VecGraphBuilder::new();
+
+    let g_nodes = g.add_nodes(num_nodes);
+
+    for n in &g_nodes {
+        nodes.push(N(n.index()));
+    }
+
+    for i in 1..g_nodes.len() {
+        g.add_edge(g_nodes[i - 1], g_nodes[i]);
+    }
+
+    // close the cycle, with 2 vertices this edge would already exist
+    if num_nodes >= 3 {
+        g.add_edge(g_nodes[num_nodes - 1], g_nodes[0]);
+    }
+
+    (g.into_graph(), nodes, num_nodes.saturating_sub(1).min(2))
+}
+
+/// creates a 2D grid graph with `rows` x `cols` vertices
+/// each vertex is connected to its four orthogonal neighbors, the vertex in row `r`
+/// and column `c` has the index `r * cols + c`
+/// the graph has max degree 4
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn grid(rows: usize, cols: usize) -> (VecGraph, Vec<Node>, usize) {
+    let num_nodes = rows * cols;
+    let mut nodes = Vec::with_capacity(num_nodes);
+    let mut g = VecGraphBuilder::new();
+
+    let g_nodes = g.add_nodes(num_nodes);
+
+    for n in &g_nodes {
+        nodes.push(N(n.index()));
+    }
+
+    for r in 0..rows {
+        for c in 0..cols {
+            let n = r * cols + c;
+
+            // connect to the right neighbor
+            if c + 1 < cols {
+                g.add_edge(g_nodes[n], g_nodes[n + 1]);
+            }
+
+            // connect to the neighbor below
+            if r + 1 < rows {
+                g.add_edge(g_nodes[n], g_nodes[n + cols]);
+            }
+        }
+    }
+
+    // a dimension contributes at most 2 neighbors, fewer if it's too short
+    let delta = if rows == 0 || cols == 0 { 0 } else { (rows - 1).min(2) + (cols - 1).min(2) };
+    (g.into_graph(), nodes, delta)
+}
+
+/// creates a king graph, a grid with `rows` x `cols` vertices where each vertex is connected
+/// to all vertices a chess king could move to, i.e. its orthogonal and diagonal neighbors
+/// the vertex in row `r` and column `c` has the index `r * cols + c` like in `grid`
+/// interior vertices have degree 8 and with at least 2 rows and columns 4 colors are needed
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn king_grid(rows: usize, cols: usize) -> (VecGraph, Vec<Node>, usize) {
+    let mut edges = Vec::new();
+
+    for r in 0..rows {
+        for c in 0..cols {
+            let n = r * cols + c;
+
+            // connect to the right neighbor
+            if c + 1 < cols {
+                edges.push((n, n + 1));
+            }
+
+            if r + 1 < rows {
+                // connect to the neighbor below and the two diagonal neighbors below
+                edges.push((n, n + cols));
+                if c + 1 < cols {
+                    edges.push((n, n + cols + 1));
+                }
+                if c > 0 {
+                    edges.push((n, n + cols - 1));
+                }
+            }
+        }
+    }
+
+    graph_from_edges(rows * cols, &edges)
+}
+
+/// creates a torus, a grid with `rows` rows and `cols` columns whose edges wrap around in both dimensions
+/// vertex `r * cols + c` is in row `r` and column `c`
+/// a dimension only wraps around if it has at least 3 vertices, otherwise the wrapping edge would
+/// already exist or be a self-loop, so with at least 3 rows and columns every vertex has degree 4
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn torus(rows: usize, cols: usize) -> (VecGraph, Vec<Node>, usize) {
+    let mut edges = Vec::new();
+
+    for r in 0..rows {
+        for c in 0..cols {
+            let n = r * cols + c;
+
+            // connect to the right neighbor, the last column wraps around to the first
+            if c + 1 < cols {
+                edges.push((n, n + 1));
+            } else if cols >= 3 {
+                edges.push((n, r * cols));
+            }
+
+            // connect to the neighbor below, the last row wraps around to the first
+            if r + 1 < rows {
+                edges.push((n, n + cols));
+            } else if rows >= 3 {
+                edges.push((n, c));
+            }
+        }
+    }
+
+    graph_from_edges(rows * cols, &edges)
+}
+
+/// creates a hypercube of the given dimension d with 2^d vertices
+/// two vertices are adjacent if their ids differ in exactly one bit, so every vertex has degree d
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn hypercube(dimension: usize) -> (VecGraph, Vec<Node>, usize) {
+    let num_nodes = 1 << dimension;
+    let mut edges = Vec::new();
+
+    for v in 0..num_nodes {
+        for bit in 0..dimension {
+            let u = v ^ (1 << bit);
+
+            // only add each edge once from the smaller id
+            if v < u {
+                edges.push((v, u));
+            }
+        }
+    }
+
+    graph_from_edges(num_nodes, &edges)
+}
+
+/// creates a caterpillar, a path of `spine_len` spine vertices where each one has `leaves_per_spine` leaves
+/// the spine vertices are `0..spine_len`, the leaves of spine vertex `i` follow after all spine vertices
+/// with at least 3 spine vertices the graph has max degree `leaves_per_spine + 2`
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn caterpillar(spine_len: usize, leaves_per_spine: usize) -> (VecGraph, Vec<Node>, usize) {
+    let num_nodes = spine_len * (leaves_per_spine + 1);
+    let mut edges = Vec::new();
+
+    for i in 0..spine_len {
+        // connect to the next spine vertex
+        if i + 1 < spine_len {
+            edges.push((i, i + 1));
+        }
+
+        for j in 0..leaves_per_spine {
+            edges.push((i, spine_len + i * leaves_per_spine + j));
+        }
+    }
+
+    graph_from_edges(num_nodes, &edges)
+}
+
+/// creates a wheel graph with `num_nodes` vertices
+/// the rim is a cycle of `num_nodes - 1` vertices which are all connected to the hub, vertex 0
+/// the hub has degree `num_nodes - 1` so the graph has max degree `num_nodes - 1`
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn wheel(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
+    let rim = num_nodes.saturating_sub(1);
+    let mut edges = Vec::new();
+
+    for i in 1..=rim {
+        // spoke from the hub
+        edges.push((0, i));
+
+        // connect to the next rim vertex, with 2 rim vertices this edge only exists once
+        if i < rim {
+            edges.push((i, i + 1));
+        }
+    }
+
+    // close the rim
+    if rim >= 3 {
+        edges.push((rim, 1));
+    }
+
+    graph_from_edges(num_nodes, &edges)
+}
+
+/// creates the friendship graph F_k, also called windmill graph, made of `num_triangles` triangles
+/// which all share the hub, vertex 0, the triangle `i` consists of the hub and the vertices `2i + 1` and `2i + 2`
+/// the hub has degree `2 * num_triangles` but the graph only needs 3 colors
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn windmill(num_triangles: usize) -> (VecGraph, Vec<Node>, usize) {
+    let mut edges = Vec::with_capacity(3 * num_triangles);
+
+    for i in 0..num_triangles {
+        let (a, b) = (2 * i + 1, 2 * i + 2);
+        edges.extend([(0, a), (0, b), (a, b)]);
+    }
+
+    graph_from_edges(2 * num_triangles + 1, &edges)
+}
+
+/// creates a barbell graph of two complete graphs with `clique_size` vertices joined by a path of `path_len` vertices
+/// the first clique has the vertices 0 to `clique_size - 1`, followed by the path and the second clique,
+/// the last vertex of the first clique and the first vertex of the second clique are the ends of the path
+/// these two vertices have degree `clique_size`, so it's the max degree unless the cliques are smaller than 2
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn barbell(clique_size: usize, path_len: usize) -> (VecGraph, Vec<Node>, usize) {
+    assert!(clique_size >= 1, "the cliques need at least one vertex");
+    let second = clique_size + path_len;
+    let mut edges = Vec::new();
+
+    for offset in [0, second] {
+        for u in 0..clique_size {
+            edges.extend((u + 1..clique_size).map(|v| (offset + u, offset + v)));
+        }
+    }
+
+    // the path from the last vertex of the first clique to the first vertex of the second clique
+    edges.extend((clique_size - 1..second).map(|v| (v, v + 1)));
+
+    graph_from_edges(second + clique_size, &edges)
+}
+
+/// creates a graph that is similar to hydrocarbon chains
+/// it will try to make the chain as long as possible using `num_nodes` nodes
+/// it there aren't enough nodes some carbon atoms will not have all hydrogen neighbors
+/// the graph has max degree 4
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn hydrocarbon(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
+    let mut nodes = Vec::with_capacity(num_nodes);
+    let mut g = VecGraphBuilder::new();
+
+    let g_nodes = g.add_nodes(num_nodes);
+    for n in &g_nodes {
+        nodes.push(N(n.index()));
+    }
+
+    // a single atom or none at all doesn't have any bonds
+    if num_nodes < 2 {
+        return (g.into_graph(), nodes, 0);
+    }
+
+    // add first hydrogen [0] and carbon [1] bond
+    g.add_edge(g_nodes[0], g_nodes[1]);
+    let mut node_counter = 2;
+    let mut last_carbon = 1;
+
+    // at the start of every iteration the next unused node is `last_carbon + 1` which is `node_counter`,
+    // so a node is only indexed after checking it exists
+    loop {
+        if node_counter == num_nodes {
+            break;
+        }
+
+        let top_hydrogen = last_carbon + 1;
+        let bottom_hydrogen = last_carbon + 2;
+        let next_carbon = last_carbon + 3;
+
+        // add top hydrogen to last carbon
+        g.add_edge(g_nodes[last_carbon], g_nodes[top_hydrogen]);
+
+        node_counter += 1;
+        if node_counter == num_nodes {
+            break;
+        }
+
+        // add bottom hydrogen to last carbon
+        g.add_edge(g_nodes[last_carbon], g_nodes[bottom_hydrogen]);
+
+        node_counter += 1;
+        if node_counter == num_nodes {
+            break;
+        }
+
+        // add new carbon or last hydrogen
+        g.add_edge(g_nodes[last_carbon], g_nodes[next_carbon]);
+        last_carbon = next_carbon;
+
+        node_counter += 1;
+        if node_counter == num_nodes {
+            break;
+        }
+    }
+
+    (g.into_graph(), nodes, (num_nodes - 1).min(4))
+}
+
+/// creates an Erdős–Rényi random graph G(n, p) with `num_nodes` vertices
+/// each of the possible edges is included independently with probability `probability`
+/// the max degree depends on the edges chosen by `rng`
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn random_graph(num_nodes: usize, probability: f64, rng: &mut impl Rng) -> (VecGraph, Vec<Node>, usize) {
+    let mut edges = Vec::new();
+
+    for u in 0..num_nodes {
+        for v in u + 1..num_nodes {
+            if rng.gen_bool(probability) {
+                edges.push((u, v));
+            }
+        }
+    }
+
+    graph_from_edges(num_nodes, &edges)
+}
+
+/// creates a random `degree`-regular graph with `num_nodes` vertices, every vertex has exactly `degree` neighbors
+/// uses the pairing model: every vertex gets `degree` points and random pairs of points are joined,
+/// pairs which would create a self-loop or a parallel edge are rejected, and if no valid pair is left
+/// the pairing is restarted from the beginning
+/// `num_nodes * degree` must be even and `degree` smaller than `num_nodes` unless both are 0
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn random_regular(num_nodes: usize, degree: usize, rng: &mut impl Rng) -> (VecGraph, Vec<Node>, usize) {
+    assert!(num_nodes * degree % 2 != 1, "num_nodes * degree must be even, every edge has two endpoints");
+    assert!(degree < num_nodes || degree == 0, "the degree must be smaller than the number of vertices");
+
+    'pairing: loop {
+        let mut points: Vec<usize> = (0..num_nodes).flat_map(|v| vec![v; degree]).collect();
+        let mut edges = HashSet::new();
+        let is_valid = |edges: &HashSet<(usize, usize)>, u: usize, v: usize| u != v && !edges.contains(&(u.min(v), u.max(v)));
+
+        while !points.is_empty() {
+            // random pairs are tried first, only if they keep failing all remaining pairs are checked
+            let mut pair = None;
+            for _ in 0..50 {
+                let i = rng.gen_range(0..points.len());
+                let j = rng.gen_range(0..points.len());
+                if i != j && is_valid(&edges, points[i], points[j]) {
+                    pair = Some((i, j));
+                    break;
+                }
+            }
+
+            if pair.is_none() {
+                let valid: Vec<(usize, usize)> = (0..points.len())
+                    .flat_map(|i| (i + 1..points.len()).map(move |j| (i, j)))
+                    .filter(|&(i, j)| is_valid(&edges, points[i], points[j]))
+                    .collect();
+                if valid.is_empty() {
+                    continue 'pairing;
+                }
+                pair = Some(valid[rng.gen_range(0..valid.len())]);
+            }
+
+            let (i, j) = pair.unwrap();
+            let (u, v) = (points[i], points[j]);
+            edges.insert((u.min(v), u.max(v)));
+
+            // remove the larger index first so the smaller one stays valid
+            points.swap_remove(i.max(j));
+            points.swap_remove(i.min(j));
+        }
+
+        // sorted so the graph only depends on the seed and not on the order of the hash set
+        let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+        edges.sort_unstable();
+        return graph_from_edges(num_nodes, &edges);
+    }
+}
+
+/// creates `num_clusters` complete graphs with `cluster_size` vertices each which are connected
+/// by `inter_edges` random edges between vertices of different clusters, like communities joined by a few bridges
+/// cluster `i` consists of the vertices `i * cluster_size..(i + 1) * cluster_size`
+/// the number of bridges is limited to the number of possible edges between different clusters
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn clustered(num_clusters: usize, cluster_size: usize, inter_edges: usize, rng: &mut impl Rng) -> (VecGraph, Vec<Node>, usize) {
+    let num_nodes = num_clusters * cluster_size;
+    let mut edges = Vec::new();
+
+    for c in 0..num_clusters {
+        let first = c * cluster_size;
+        for u in first..first + cluster_size {
+            for v in u + 1..first + cluster_size {
+                edges.push((u, v));
+            }
+        }
+    }
+
+    let possible = num_nodes * num_nodes.saturating_sub(cluster_size) / 2;
+    let mut bridges = HashSet::new();
+    while bridges.len() < inter_edges.min(possible) {
+        let u = rng.gen_range(0..num_nodes);
+        let v = rng.gen_range(0..num_nodes);
+        if u / cluster_size != v / cluster_size && bridges.insert((u.min(v), u.max(v))) {
+            edges.push((u.min(v), u.max(v)));
+        }
+    }
+
+    graph_from_edges(num_nodes, &edges)
+}
+
+/// creates a balanced k-ary tree with `num_nodes` vertices
+/// the vertices are numbered in breadth first order, so the parent of vertex `i` is `(i - 1) / k`
+/// each internal vertex has up to `k` children, the graph has max degree `k + 1`
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn kary_tree(num_nodes: usize, k: usize) -> (VecGraph, Vec<Node>, usize) {
+    assert!(k >= 1, "a tree needs a branching factor of at least 1");
+    let edges: Vec<(usize, usize)> = (1..num_nodes).map(|i| ((i - 1) / k, i)).collect();
+
+    graph_from_edges(num_nodes, &edges)
+}
+
+/// creates the Petersen graph with 10 vertices, the generalized Petersen graph GP(5, 2)
+/// vertices 0 to 4 form the outer 5-cycle, vertices 5 to 9 the inner pentagram
+/// and each outer vertex `i` is connected to the inner vertex `i + 5` by a spoke
+/// the graph is 3-regular and has chromatic number 3
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn petersen() -> (VecGraph, Vec<Node>, usize) {
+    generalized_petersen(5, 2)
+}
+
+/// creates the generalized Petersen graph GP(n, k) with `2 * n` vertices
+/// vertices 0 to n - 1 form the outer n-cycle, the inner vertex `n + i` is connected to the inner vertex
+/// `n + (i + k) % n` and each outer vertex `i` is connected to the inner vertex `n + i` by a spoke
+/// `n` must be at least 3 and `k` between 1 and (n - 1) / 2, then the graph is 3-regular
+/// it's bipartite if `n` is even and `k` odd and needs 3 colors otherwise
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn generalized_petersen(n: usize, k: usize) -> (VecGraph, Vec<Node>, usize) {
+    assert!(n >= 3, "the outer cycle needs at least 3 vertices");
+    assert!(k >= 1 && 2 * k < n, "the step must be between 1 and (n - 1) / 2");
+
+    // outer cycle
+    let mut edges: Vec<(usize, usize)> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+    // inner star polygon
+    edges.extend((0..n).map(|i| (n + i, n + (i + k) % n)));
+    // spokes
+    edges.extend((0..n).map(|i| (i, n + i)));
+
+    graph_from_edges(2 * n, &edges)
+}
+
+/// creates the Mycielski graph M_k which is triangle-free but has chromatic number `k`
+/// M_1 is a single vertex, M_2 a single edge and M_k is the Mycielskian of M_(k-1):
+/// for every vertex `v` a copy `u` adjacent to the neighbors of `v` is added and a vertex `w` adjacent to all copies
+/// the graph has `3 * 2^(k-2) - 1` vertices for k >= 2
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn mycielski(k: usize) -> (VecGraph, Vec<Node>, usize) {
+    assert!(k >= 1, "the Mycielski graph needs a chromatic number of at least 1");
+    if k == 1 {
+        return graph_from_edges(1, &[]);
+    }
+
+    let mut num_nodes = 2;
+    let mut edges = vec![(0, 1)];
+
+    for _ in 2..k {
+        // the copy of vertex `v` is `num_nodes + v` and `w` is the last vertex
+        let w = 2 * num_nodes;
+        let mut next = edges.clone();
+        for &(v1, v2) in &edges {
+            next.push((num_nodes + v1, v2));
+            next.push((v1, num_nodes + v2));
+        }
+        for v in 0..num_nodes {
+            next.push((num_nodes + v, w));
+        }
+
+        edges = next;
+        num_nodes = w + 1;
+    }
+
+    graph_from_edges(num_nodes, &edges)
+}
+
+/// creates the disjoint union of two graphs, there are no edges between the two parts
+/// the vertices of `first` keep their index and vertex `v` of `second` becomes `first.num_nodes() + v`
+/// e.g. adding `complete_graph(1)` adds an isolated vertex
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn disjoint_union(first: &VecGraph, second: &VecGraph) -> (VecGraph, Vec<Node>, usize) {
+    let offset = first.num_nodes();
+    let edges: Vec<(usize, usize)> = first.edges()
+        .map(|e| first.enodes(e))
+        .map(|(u, v)| (u.index(), v.index()))
+        .chain(second.edges()
+            .map(|e| second.enodes(e))
+            .map(|(u, v)| (offset + u.index(), offset + v.index())))
+        .collect();
+
+    graph_from_edges(offset + second.num_nodes(), &edges)
+}
+
+/// creates the complement of the graph, two vertices are adjacent exactly if they aren't adjacent in `graph`
+/// the vertices keep their index, e.g. the complement of a complete graph has no edges at all
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn complement(graph: &VecGraph) -> (VecGraph, Vec<Node>, usize) {
+    let num_nodes = graph.num_nodes();
+    let adjacent: HashSet<(usize, usize)> = graph.edges()
+        .map(|e| graph.enodes(e))
+        .map(|(u, v)| (u.index().min(v.index()), u.index().max(v.index())))
+        .collect();
+
+    let mut edges = Vec::new();
+    for u in 0..num_nodes {
+        for v in u + 1..num_nodes {
+            if !adjacent.contains(&(u, v)) {
+                edges.push((u, v));
+            }
+        }
+    }
+
+    graph_from_edges(num_nodes, &edges)
+}
@@ -0,0 +1,91 @@
+use crate::{Color, Coloring};
+use crate::Coloring::Permanent;
+
+/// a set of colors stored as a bitset, clearing it keeps the memory so it can be reused every round
+#[derive(Clone, Debug, Default)]
+struct ColorSet {
+    words: Vec<u64>,
+}
+
+impl ColorSet {
+    fn insert(&mut self, color: Color) {
+        let word = color / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (color % 64);
+    }
+
+    fn contains(&self, color: Color) -> bool {
+        self.words.get(color / 64).is_some_and(|w| w & (1 << (color % 64)) != 0)
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// returns the colors in the set in ascending order
+    fn iter(&self) -> impl Iterator<Item = Color> + '_ {
+        (0..self.words.len() * 64).filter(|&c| self.contains(c))
+    }
+}
+
+/// the colors a node received from its neighbors in the last round it was a candidate,
+/// it's cleared when the next messages arrive so the final state of a node still shows why it went permanent
+/// the messages are aggregated as they arrive instead of being stored, so a node with a high degree
+/// doesn't collect a list of messages which is scanned afterwards and no memory is allocated every round
+#[derive(Clone, Debug, Default)]
+pub struct Inbox {
+    /// the number of messages received
+    received: usize,
+    /// the colors used by a neighbor, permanently or as a candidate
+    used: ColorSet,
+    /// the colors used permanently by a neighbor
+    permanent: ColorSet,
+}
+
+impl Inbox {
+    /// adds the coloring sent by a neighbor
+    pub(crate) fn push(&mut self, coloring: Coloring) {
+        self.received += 1;
+        self.used.insert(*coloring.color());
+        if let Permanent(c) = coloring {
+            self.permanent.insert(c);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.received = 0;
+        self.used.clear();
+        self.permanent.clear();
+    }
+
+    /// returns whether a neighbor uses the color, permanently or as a candidate
+    pub(crate) fn is_used(&self, color: Color) -> bool {
+        self.used.contains(color)
+    }
+
+    /// returns whether a neighbor uses the color permanently
+    pub(crate) fn is_permanent(&self, color: Color) -> bool {
+        self.permanent.contains(color)
+    }
+
+    /// returns the number of messages received
+    pub fn len(&self) -> usize {
+        self.received
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.received == 0
+    }
+
+    /// returns the colors used by a neighbor in ascending order
+    pub fn colors(&self) -> impl Iterator<Item = Color> + '_ {
+        self.used.iter()
+    }
+
+    /// returns the colors used permanently by a neighbor in ascending order
+    pub fn permanent_colors(&self) -> impl Iterator<Item = Color> + '_ {
+        self.permanent.iter()
+    }
+}
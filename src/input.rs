@@ -0,0 +1,374 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+
+use rs_graph::VecGraph;
+use rs_graph::traits::{FiniteGraph, Indexable};
+
+use crate::builder::GraphBuilder;
+use crate::{Color, Node};
+
+/// builds a graph with `num_nodes` vertices from a list of undirected edges
+/// like in the generators each undirected edge is only stored once
+/// returns the graph, a vector of nodes and delta (max degree)
+pub(crate) fn graph_from_edges(num_nodes: usize, edges: &[(usize, usize)]) -> (VecGraph, Vec<Node>, usize) {
+    let mut builder = GraphBuilder::new(num_nodes);
+
+    for &(u, v) in edges {
+        builder.add_edge(u, v);
+    }
+
+    builder.build()
+}
+
+/// reads the whole file, if the path is `-` standard input is read until the end instead
+fn read_input(path: &str) -> Result<String, String> {
+    if path == "-" {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)
+            .map_err(|e| format!("Reading from stdin failed: {e}"))?;
+        return Ok(content);
+    }
+
+    fs::read_to_string(path).map_err(|e| format!("Reading '{path}' failed: {e}"))
+}
+
+/// collects the undirected edges read from a file
+/// self-loops are skipped because a vertex can never have a different color than itself,
+/// parallel edges are only added once, a warning is printed for both
+/// unless `silent_duplicates` is set, e.g. for formats where parallel edges are merged anyway
+#[derive(Default)]
+struct EdgeSet {
+    edges: Vec<(usize, usize)>,
+    seen: HashSet<(usize, usize)>,
+    silent_duplicates: bool,
+    /// added to the vertex indices in warnings, so they match the file for formats which aren't 0-indexed
+    id_offset: usize,
+}
+
+impl EdgeSet {
+    /// `location` tells the user where the edge came from in warnings, e.g. `line 3`
+    fn add(&mut self, u: usize, v: usize, location: impl Display) {
+        let (id_u, id_v) = (u + self.id_offset, v + self.id_offset);
+        if u == v {
+            eprintln!("warning: {location}: skipping self-loop {id_u} {id_v}, a vertex can't be colored differently from itself");
+            return;
+        }
+
+        if !self.seen.insert((u.min(v), u.max(v))) {
+            if self.silent_duplicates {
+                return;
+            }
+            eprintln!("warning: {location}: skipping duplicate edge {id_u} {id_v}");
+            return;
+        }
+
+        self.edges.push((u, v));
+    }
+}
+
+/// builds a graph from edges given directly, e.g. on the command line
+/// self-loops and duplicate edges are skipped with a warning like in an edge list file
+/// the number of vertices is the largest index plus one
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn graph_from_edge_pairs(pairs: &[(usize, usize)]) -> (VecGraph, Vec<Node>, usize) {
+    let mut edges = EdgeSet::default();
+    let mut num_nodes = 0;
+
+    for (i, &(u, v)) in pairs.iter().enumerate() {
+        num_nodes = num_nodes.max(u + 1).max(v + 1);
+        edges.add(u, v, format_args!("edge {}", i + 1));
+    }
+
+    graph_from_edges(num_nodes, &edges.edges)
+}
+
+/// parses an edge list where each line contains two whitespace separated vertex indices `u v`
+/// a line with a single index `v` only adds the vertex, so isolated vertices at the end aren't lost
+/// blank lines and everything after a `#` is ignored, vertices are 0-indexed
+/// self-loops and duplicate edges are skipped with a warning
+/// the number of vertices is the largest index plus one
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn parse_edge_list(content: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let mut edges = EdgeSet::default();
+    let mut num_nodes = 0;
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parse_vertex = |s: &str| s.parse::<usize>()
+            .map_err(|e| format!("line {}: invalid vertex '{s}': {e}", line_number + 1));
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() == 1 {
+            num_nodes = num_nodes.max(parse_vertex(fields[0])? + 1);
+            continue;
+        }
+        if fields.len() != 2 {
+            return Err(format!("line {}: expected an edge 'u v' but found '{line}'", line_number + 1));
+        }
+
+        let u = parse_vertex(fields[0])?;
+        let v = parse_vertex(fields[1])?;
+        num_nodes = num_nodes.max(u + 1).max(v + 1);
+        edges.add(u, v, format_args!("line {}", line_number + 1));
+    }
+
+    Ok(graph_from_edges(num_nodes, &edges.edges))
+}
+
+/// writes the graph as an edge list which `parse_edge_list` reads back into the same graph
+/// every undirected edge is written once as `u v` in the order it's stored,
+/// isolated vertices are written on their own line so the number of vertices is kept
+pub fn format_edge_list(graph: &VecGraph) -> String {
+    let mut content = String::new();
+    let mut isolated = vec![true; graph.num_nodes()];
+
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        isolated[u.index()] = false;
+        isolated[v.index()] = false;
+        content.push_str(&format!("{} {}\n", u.index(), v.index()));
+    }
+
+    for v in (0..graph.num_nodes()).filter(|&v| isolated[v]) {
+        content.push_str(&format!("{v}\n"));
+    }
+
+    content
+}
+
+/// reads a graph from an edge list file or stdin if the path is `-`, see `parse_edge_list` for the format
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn load_edge_list(path: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let content = read_input(path)?;
+    parse_edge_list(&content).map_err(|e| format!("Parsing '{path}' failed: {e}"))
+}
+
+/// parses a graph in the DIMACS format used by graph coloring benchmarks
+/// lines starting with `c` are comments, the header `p edge N M` gives the number of vertices
+/// and edges and each edge is given as `e u v`, vertices are 1-indexed
+/// self-loops are skipped with a warning, duplicate edges are merged silently
+/// because many benchmark files list every edge in both directions
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn parse_dimacs(content: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let mut edges = EdgeSet { silent_duplicates: true, id_offset: 1, ..Default::default() };
+    let mut num_nodes = None;
+
+    for (line_number, line) in content.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let error = |msg: String| format!("line {}: {msg}", line_number + 1);
+
+        match fields.first() {
+            None | Some(&"c") => {}
+            Some(&"p") => {
+                if num_nodes.is_some() {
+                    return Err(error("found a second problem line".to_string()));
+                }
+                if fields.len() != 4 || (fields[1] != "edge" && fields[1] != "col") {
+                    return Err(error(format!("expected a problem line 'p edge N M' but found '{line}'")));
+                }
+                let n = fields[2].parse::<usize>()
+                    .map_err(|e| error(format!("invalid number of vertices '{}': {e}", fields[2])))?;
+                num_nodes = Some(n);
+            }
+            Some(&"e") => {
+                let n = num_nodes.ok_or_else(|| error("found an edge before the problem line".to_string()))?;
+                if fields.len() != 3 {
+                    return Err(error(format!("expected an edge 'e u v' but found '{line}'")));
+                }
+
+                let parse_vertex = |s: &str| match s.parse::<usize>() {
+                    Ok(v) if v >= 1 && v <= n => Ok(v - 1),
+                    Ok(v) => Err(error(format!("vertex {v} is out of range 1..={n}"))),
+                    Err(e) => Err(error(format!("invalid vertex '{s}': {e}"))),
+                };
+
+                edges.add(parse_vertex(fields[1])?, parse_vertex(fields[2])?, format_args!("line {}", line_number + 1));
+            }
+            Some(other) => return Err(error(format!("unknown line type '{other}'"))),
+        }
+    }
+
+    let num_nodes = num_nodes.ok_or("missing problem line 'p edge N M'")?;
+    Ok(graph_from_edges(num_nodes, &edges.edges))
+}
+
+/// reads a graph from a DIMACS file or stdin if the path is `-`, see `parse_dimacs` for the format
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn load_dimacs(path: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let content = read_input(path)?;
+    parse_dimacs(&content).map_err(|e| format!("Parsing '{path}' failed: {e}"))
+}
+
+/// parses a graph in the DOT format as written by this tool
+/// only undirected graphs `graph { ... }` or `strict graph { ... }` with vertices named `nX` are supported,
+/// statements are edges `nX -- nY`, possibly chained, or vertices `nX` with optional attributes,
+/// other statements like graph attributes are ignored, vertices are 0-indexed
+/// in a strict graph parallel edges are merged, otherwise they are skipped with a warning
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn parse_dot(content: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let open = content.find('{').ok_or("missing '{' after the graph header")?;
+    let close = content.rfind('}').ok_or("missing '}' at the end of the graph")?;
+    if close < open {
+        return Err("missing '}' at the end of the graph".to_string());
+    }
+
+    let header: Vec<&str> = content[..open].split_whitespace().collect();
+    let strict = header.first() == Some(&"strict");
+    match header.get(strict as usize) {
+        Some(&"graph") => {}
+        Some(&"digraph") => return Err("directed graphs are not supported, the graph must be undirected".to_string()),
+        _ => return Err(format!("expected a header 'graph {{' or 'strict graph {{' but found '{}'", header.join(" "))),
+    }
+
+    let mut edges = EdgeSet { silent_duplicates: strict, ..Default::default() };
+    let mut num_nodes = 0;
+
+    // the line number of the opening brace, statements are counted from there
+    let first_line = content[..open].lines().count().max(1);
+
+    for (line_offset, line) in content[open + 1..close].lines().enumerate() {
+        let line_number = first_line + line_offset;
+
+        for statement in line.split(';').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            if statement.contains("->") {
+                return Err(format!("line {line_number}: directed edges '->' are not supported"));
+            }
+
+            // remove the attributes of the statement
+            let statement = statement.split('[').next().unwrap().trim();
+
+            let vertices: Vec<&str> = statement.split("--").map(|v| v.trim()).collect();
+            if vertices.len() == 1 && !is_dot_vertex(vertices[0]) {
+                // graph attributes or default node and edge attributes
+                continue;
+            }
+
+            let mut indices = Vec::with_capacity(vertices.len());
+            for v in vertices {
+                if !is_dot_vertex(v) {
+                    return Err(format!("line {line_number}: invalid vertex '{v}', vertices must be named nX"));
+                }
+                let index = v[1..].parse::<usize>()
+                    .map_err(|e| format!("line {line_number}: invalid vertex '{v}': {e}"))?;
+                num_nodes = num_nodes.max(index + 1);
+                indices.push(index);
+            }
+
+            for pair in indices.windows(2) {
+                edges.add(pair[0], pair[1], format_args!("line {line_number}"));
+            }
+        }
+    }
+
+    Ok(graph_from_edges(num_nodes, &edges.edges))
+}
+
+/// checks if the name is a vertex name `nX` as written by this tool
+fn is_dot_vertex(name: &str) -> bool {
+    name.len() > 1 && name.starts_with('n') && name[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// reads a graph from a DOT file or stdin if the path is `-`, see `parse_dot` for the supported subset of the format
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn load_dot(path: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let content = read_input(path)?;
+    parse_dot(&content).map_err(|e| format!("Parsing '{path}' failed: {e}"))
+}
+
+/// parses a graph given as a square adjacency matrix with one row per line
+/// the entries are whitespace separated `0` or `1`, blank lines are ignored
+/// the matrix must be symmetric, so only the upper triangle is used to add the edges,
+/// a `1` on the diagonal is a self-loop and skipped with a warning
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn parse_adjacency_matrix(content: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let mut rows = Vec::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let row = line.split_whitespace()
+            .map(|entry| match entry {
+                "0" => Ok(false),
+                "1" => Ok(true),
+                _ => Err(format!("line {}: invalid entry '{entry}', entries must be 0 or 1", line_number + 1)),
+            })
+            .collect::<Result<Vec<bool>, String>>()?;
+        rows.push((line_number + 1, row));
+    }
+
+    let num_nodes = rows.len();
+    for (line_number, row) in &rows {
+        if row.len() != num_nodes {
+            return Err(format!("line {line_number}: the matrix has {num_nodes} rows but this row has {} entries, it must be square", row.len()));
+        }
+    }
+
+    let mut edges = EdgeSet::default();
+    for (u, (line_number, row)) in rows.iter().enumerate() {
+        for v in u..num_nodes {
+            if row[v] != rows[v].1[u] {
+                return Err(format!("line {line_number}: the matrix is not symmetric, entry ({u}, {v}) differs from ({v}, {u})"));
+            }
+            if row[v] {
+                edges.add(u, v, format_args!("line {line_number}"));
+            }
+        }
+    }
+
+    Ok(graph_from_edges(num_nodes, &edges.edges))
+}
+
+/// reads a graph from an adjacency matrix file or stdin if the path is `-`, see `parse_adjacency_matrix` for the format
+/// returns the graph, a vector of nodes and delta (max degree)
+pub fn load_adjacency_matrix(path: &str) -> Result<(VecGraph, Vec<Node>, usize), String> {
+    let content = read_input(path)?;
+    parse_adjacency_matrix(&content).map_err(|e| format!("Parsing '{path}' failed: {e}"))
+}
+
+/// parses a coloring of a graph with `num_nodes` vertices given as CSV with one `id,color` pair per line
+/// like the output of `-f csv`, the header `id,color`, blank lines and everything after a `#` are ignored
+/// every vertex must get exactly one color, the ids start at `id_offset` like the output of `--one-indexed`
+/// returns the color of every vertex
+pub fn parse_coloring(content: &str, num_nodes: usize, id_offset: usize) -> Result<Vec<Color>, String> {
+    let mut colors = vec![None; num_nodes];
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() || line == "id,color" {
+            continue;
+        }
+
+        let (id, color) = line.split_once(',')
+            .ok_or_else(|| format!("line {}: expected 'id,color' but found '{line}'", line_number + 1))?;
+        let id: usize = id.trim().parse()
+            .map_err(|e| format!("line {}: invalid node '{id}': {e}", line_number + 1))?;
+        let color: Color = color.trim().parse()
+            .map_err(|e| format!("line {}: invalid color '{color}': {e}", line_number + 1))?;
+
+        let Some(v) = id.checked_sub(id_offset).filter(|&v| v < num_nodes) else {
+            return Err(format!("line {}: the node {id} doesn't exist, the ids go from {id_offset} to {}", line_number + 1, num_nodes + id_offset - 1));
+        };
+        if colors[v].replace(color).is_some() {
+            return Err(format!("line {}: the node {id} is colored twice", line_number + 1));
+        }
+    }
+
+    colors.iter().enumerate()
+        .map(|(v, color)| color.ok_or_else(|| format!("the node {} doesn't have a color", v + id_offset)))
+        .collect()
+}
+
+/// reads a coloring from a CSV file or stdin if the path is `-`, see `parse_coloring` for the format
+/// returns the color of every vertex
+pub fn load_coloring(path: &str, num_nodes: usize, id_offset: usize) -> Result<Vec<Color>, String> {
+    let content = read_input(path)?;
+    parse_coloring(&content, num_nodes, id_offset).map_err(|e| format!("Parsing '{path}' failed: {e}"))
+}
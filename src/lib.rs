@@ -0,0 +1,671 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::time::{Duration, Instant};
+
+use rand::{Rng, SeedableRng};
+use rand::prelude::{IteratorRandom, SliceRandom};
+use rand::rngs::StdRng;
+use rs_graph::VecGraph;
+use rs_graph::traits::{FiniteGraph, Indexable};
+
+use crate::Coloring::{Candidate, Permanent};
+use crate::inbox::Inbox;
+use crate::logger::{LogLevel, Logger};
+
+pub mod builder;
+pub mod generators;
+pub mod inbox;
+pub mod input;
+pub mod logger;
+pub mod palette;
+pub mod sequential;
+
+/// a color is represented by its index in the list of available colors
+pub type Color = usize;
+
+/// the coloring of a node, a candidate color may still change while a permanent color is fixed
+#[derive(Copy, Clone, Debug)]
+pub enum Coloring {
+    Permanent(Color),
+    Candidate(Color),
+}
+
+impl Coloring {
+    pub fn color(&self) -> &Color {
+        match self {
+            Permanent(v) => { v }
+            Candidate(v) => { v }
+        }
+    }
+}
+
+/// a node of the graph taking part in the distributed algorithm
+/// `id` is the index of the corresponding vertex in the `VecGraph`
+/// nodes are equal if they have the same id, their coloring and inbox are ignored
+#[derive(Clone, Debug)]
+pub struct Node {
+    pub id: usize,
+    pub coloring: Coloring,
+    inbox: Inbox,
+}
+
+impl Node {
+    pub fn new(id: usize) -> Node {
+        Node {
+            id,
+            coloring: Candidate(id),
+            inbox: Inbox::default(),
+        }
+    }
+
+    /// returns the colors received from the neighbors which haven't been processed yet
+    pub fn inbox(&self) -> &Inbox {
+        &self.inbox
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Node {}
+
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// the default number of rounds after which the algorithm gives up
+pub const DEFAULT_MAX_ROUNDS: usize = 10000;
+
+/// the minimum time between two progress reports
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// how the nodes are scheduled within a round of the algorithm
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Schedule {
+    /// all nodes exchange their colors and then all nodes update their colors
+    Synchronous,
+    /// the nodes update one after another in a random order, reading the current colors of their neighbors
+    Asynchronous,
+}
+
+/// how the candidate colors of the first round are chosen
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InitialColoring {
+    /// every node chooses a random color
+    Random,
+    /// node `id` gets the color `id % num_colors`, so every run starts from the same coloring
+    Deterministic,
+}
+
+/// options for the distributed randomized coloring algorithm
+#[derive(Clone, Debug)]
+pub struct AlgorithmOptions {
+    /// the number of rounds after which the algorithm gives up
+    pub max_rounds: usize,
+    pub schedule: Schedule,
+    pub initial_coloring: InitialColoring,
+    /// probability with which a node choosing a new color takes the smallest available color
+    /// instead of a random one, 0 chooses uniformly at random
+    pub color_bias: f64,
+    /// receives diagnostic messages about every step, nothing is logged without a logger
+    pub logger: Option<Logger>,
+    /// print the number of remaining candidate nodes to stderr, at most every `PROGRESS_INTERVAL`
+    pub progress: bool,
+    /// print the coloring to stderr and wait for a line on stdin after every round to step through the algorithm
+    pub step: bool,
+    /// the time after which the algorithm gives up, checked at the start of every round
+    pub timeout: Option<Duration>,
+    /// if not empty the algorithm stops as soon as all of these nodes have a permanent color,
+    /// the other nodes keep their current color which may still be a candidate color
+    pub priority_nodes: Vec<usize>,
+    /// nodes given as `(id, color)` which start with this permanent color, their neighbors have to avoid it
+    pub precolored: Vec<(usize, Color)>,
+    /// probability with which each message is lost, a node which didn't receive all colors of its neighbors
+    /// keeps its candidate color for this round since it can't know whether its color is free
+    pub drop_prob: f64,
+}
+
+impl Default for AlgorithmOptions {
+    fn default() -> Self {
+        AlgorithmOptions {
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            schedule: Schedule::Synchronous,
+            initial_coloring: InitialColoring::Random,
+            color_bias: 0.0,
+            logger: None,
+            progress: false,
+            step: false,
+            timeout: None,
+            priority_nodes: Vec::new(),
+            precolored: Vec::new(),
+            drop_prob: 0.0,
+        }
+    }
+}
+
+/// statistics collected while running the algorithm
+/// `messages` counts every color that was sent to a neighbor over all rounds
+/// `dropped` counts the messages which were lost on the way and aren't included in `messages`
+/// `newly_fixed[i]` is the number of nodes that got a permanent color in round `i + 1`
+#[derive(Clone, Debug, Default)]
+pub struct AlgorithmStats {
+    pub rounds: usize,
+    pub messages: usize,
+    pub dropped: usize,
+    pub newly_fixed: Vec<usize>,
+}
+
+/// the statistics of a run which didn't start
+static NO_STATS: AlgorithmStats = AlgorithmStats { rounds: 0, messages: 0, dropped: 0, newly_fixed: Vec::new() };
+
+/// the reason why the algorithm stopped without every node having a permanent color
+/// contains the statistics collected until the algorithm stopped
+#[derive(Clone, Debug)]
+pub enum ColoringError {
+    /// the algorithm ran for the maximum number of rounds without converging
+    RoundLimit(AlgorithmStats),
+    /// the algorithm ran longer than the timeout without converging
+    Timeout(AlgorithmStats),
+    /// the options don't fit the graph, e.g. a precolored or priority node doesn't exist, the algorithm didn't start
+    InvalidOptions(String),
+}
+
+impl ColoringError {
+    /// returns the statistics collected until the algorithm stopped, they are empty if it didn't start
+    pub fn stats(&self) -> &AlgorithmStats {
+        match self {
+            ColoringError::RoundLimit(stats) => stats,
+            ColoringError::Timeout(stats) => stats,
+            ColoringError::InvalidOptions(_) => &NO_STATS,
+        }
+    }
+}
+
+impl std::fmt::Display for ColoringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColoringError::RoundLimit(stats) => write!(f, "hit the round limit after {} rounds", stats.rounds),
+            ColoringError::Timeout(stats) => write!(f, "timed out after {} rounds", stats.rounds),
+            ColoringError::InvalidOptions(message) => write!(f, "invalid options: {message}"),
+        }
+    }
+}
+
+/// the result of running the coloring algorithm
+/// `coloring` contains the final color of every node indexed by node id
+#[derive(Clone, Debug)]
+pub struct ColoringResult {
+    pub rounds: usize,
+    pub messages: usize,
+    pub newly_fixed: Vec<usize>,
+    pub coloring: Vec<Color>,
+}
+
+/// returns for every vertex the list of its neighbors
+/// every undirected edge is only stored once, so it's used in both directions
+pub(crate) fn neighbors(graph: &VecGraph, num_nodes: usize) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); num_nodes];
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        neighbors[u.index()].push(v.index());
+        neighbors[v.index()].push(u.index());
+    }
+    neighbors
+}
+
+/// applies `f` to every item, with the `parallel` feature the items are split into chunks
+/// which are processed by one thread per available core
+/// scoped std threads are used instead of a thread pool crate like rayon, so the feature doesn't add a dependency
+#[cfg(feature = "parallel")]
+fn for_each_node<T: Send>(items: &mut [T], f: impl Fn(&mut T) + Sync) {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = items.len().div_ceil(threads).max(1);
+
+    std::thread::scope(|s| {
+        for chunk in items.chunks_mut(chunk_size) {
+            s.spawn(|| chunk.iter_mut().for_each(&f));
+        }
+    });
+}
+
+/// applies `f` to every item, with the `parallel` feature the items are processed by multiple threads
+#[cfg(not(feature = "parallel"))]
+fn for_each_node<T>(items: &mut [T], f: impl Fn(&mut T)) {
+    items.iter_mut().for_each(f);
+}
+
+/// the colors a node which couldn't go permanent chooses its next candidate color from
+struct Choices {
+    /// the colors not used permanently by a neighbor in ascending order
+    available: Vec<Color>,
+    /// the smallest available color which no neighbor used in this round, the choice of the color bias
+    smallest_free: Option<Color>,
+}
+
+/// computes the colors a candidate node can choose from using the colors of its neighbors in the inbox
+/// if no neighbor uses the color of the node it goes permanent and `None` is returned,
+/// otherwise the colors not permanently used by a neighbor are returned
+fn process_candidate(node: &mut Node, num_colors: usize, logger: Option<&Logger>) -> Option<Choices> {
+    log_message!(logger, LogLevel::Trace, "node {:3} is none permanent", node.id);
+    log_message!(logger, LogLevel::Trace, "node {:3}: non permanent colors {:?}", node.id,
+                 (0..num_colors).filter(|&c| !node.inbox.is_permanent(c)).collect::<Vec<_>>());
+    log_message!(logger, LogLevel::Trace, "node {:3}: colors not used by neighbors {:?}", node.id,
+                 (0..num_colors).filter(|&c| !node.inbox.is_used(c)).collect::<Vec<_>>());
+
+    // check if node can go permanent
+    if !node.inbox.is_used(*node.coloring.color()) {
+        log_message!(logger, LogLevel::Trace, "node {:3}: my color {:?} is used by nobody lets go permanent", node.id, node.coloring);
+        node.coloring = Permanent(*node.coloring.color());
+        return None;
+    }
+
+    let choices = Choices {
+        available: (0..num_colors).filter(|&c| !node.inbox.is_permanent(c)).collect(),
+        smallest_free: (0..num_colors).find(|&c| !node.inbox.is_used(c)),
+    };
+    Some(choices)
+}
+
+/// chooses a random color from the available colors
+/// with probability `color_bias` the smallest color no neighbor used in this round is chosen instead,
+/// if there is no such color a random one is chosen
+fn pick_color(choices: &Choices, color_bias: f64, rng: &mut impl Rng) -> Color {
+    // without a bias no extra random number is drawn, so the colors only depend on the seed as before
+    if color_bias > 0.0 && rng.gen_bool(color_bias) {
+        if let Some(color) = choices.smallest_free {
+            return color;
+        }
+    }
+    *choices.available.iter().choose(rng).unwrap()
+}
+
+/// chooses a new random candidate color for the node from the available colors
+/// if there are fewer colors than delta + 1 the neighbors may have taken all colors permanently,
+/// then the node keeps its color and can never be fixed
+fn choose_new_color(node: &mut Node, choices: &Choices, color_bias: f64, rng: &mut impl Rng, logger: Option<&Logger>) {
+    if choices.available.is_empty() {
+        log_message!(logger, LogLevel::Trace, "node {:3} cannot be fixed and has no colors left", node.id);
+        return;
+    }
+
+    node.coloring = Candidate(pick_color(choices, color_bias, rng));
+
+    log_message!(logger, LogLevel::Trace, "node {:3} cannot be fixed chose new color {:?}", node.id, node.coloring);
+}
+
+/// returns the color of every precolored node, an error if a node doesn't exist, its color isn't available
+/// or it's precolored with two different colors
+fn check_precolored(precolored: &[(usize, Color)], num_nodes: usize, num_colors: usize) -> Result<BTreeMap<usize, Color>, ColoringError> {
+    let mut colors = BTreeMap::new();
+    for &(v, color) in precolored {
+        if v >= num_nodes {
+            return Err(ColoringError::InvalidOptions(format!("the precolored node {v} doesn't exist, the graph only has {num_nodes} nodes")));
+        }
+        if color >= num_colors {
+            return Err(ColoringError::InvalidOptions(format!("the precolored node {v} has color {color} but only {num_colors} colors are available")));
+        }
+        if colors.insert(v, color).is_some_and(|c| c != color) {
+            return Err(ColoringError::InvalidOptions(format!("the node {v} is precolored with two different colors")));
+        }
+    }
+    Ok(colors)
+}
+
+/// runs the distributed randomized coloring algorithm with the colors `0..num_colors` using the given rng
+/// with delta + 1 colors every node always has a free color, with fewer colors than the chromatic number
+/// the algorithm can't converge and only stops at the round limit
+/// afterwards every node has a permanent color, returns the number of rounds taken and messages sent
+/// the first round in which the nodes exchange their initial colors counts as round 1
+/// if the nodes haven't converged after `options.max_rounds` rounds or before `options.timeout` an error is returned
+/// and the nodes which couldn't be fixed keep their candidate color, invalid precolored or priority nodes are an error too
+pub fn distributed_randomized_coloring_algorithm(graph: &VecGraph, nodes: &mut [Node], num_colors: usize,
+                                                 options: &AlgorithmOptions, rng: &mut impl Rng) -> Result<AlgorithmStats, ColoringError> {
+    let logger = options.logger.as_ref();
+    let start = Instant::now();
+
+    // the colors are always kept in ascending order so that the random choices only depend on the seed
+    assert!(num_colors > 0, "at least one color is needed");
+    assert!((0.0..=1.0).contains(&options.drop_prob), "the drop probability must be between 0 and 1");
+    // with a bias of 1 two neighbors can keep choosing the same smallest free color forever
+    assert!((0.0..1.0).contains(&options.color_bias), "the color bias must be at least 0 and smaller than 1");
+    let list_of_colors: Vec<Color> = (0..num_colors).collect();
+
+    log_message!(logger, LogLevel::Info, "Starting algorithm with {num_colors} colors");
+    let mut round = 1;
+    let mut messages = 0;
+    let mut dropped = 0;
+    let mut newly_fixed = Vec::new();
+    let precolored = check_precolored(&options.precolored, nodes.len(), num_colors)?;
+    if let Some(v) = options.priority_nodes.iter().find(|&&v| v >= nodes.len()) {
+        return Err(ColoringError::InvalidOptions(format!("the priority node {v} doesn't exist, the graph only has {} nodes", nodes.len())));
+    }
+    let mut remaining = nodes.len() - precolored.len();
+    let mut last_progress: Option<Instant> = None;
+
+    // the neighbors every node receives messages from, every edge carries messages in both directions
+    let neighbors = neighbors(graph, nodes.len());
+
+    // in the first round only the precolored nodes have a permanent color, everybody else chooses from all colors
+    for node in nodes.iter_mut() {
+        if let Some(&color) = precolored.get(&node.id) {
+            node.coloring = Permanent(color);
+            log_message!(logger, LogLevel::Trace, "node {:3} is precolored with color {color}", node.id);
+            continue;
+        }
+
+        // the bias only applies to new colors, all nodes starting with the smallest color would just collide
+        let color = match options.initial_coloring {
+            InitialColoring::Random => *list_of_colors.iter().choose(rng).unwrap(),
+            InitialColoring::Deterministic => node.id % num_colors,
+        };
+        node.coloring = Candidate(color);
+        log_message!(logger, LogLevel::Trace, "node {:3} chose color {:?}", node.id, node.coloring);
+    }
+
+    let has_candidate_color = |n: &&mut Node| match n.coloring {
+        Candidate(_) => true,
+        Permanent(_) => false
+    };
+
+    loop {
+        if options.timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            log_message!(logger, LogLevel::Info, "stopping before round {round}, the timeout was reached");
+            return Err(ColoringError::Timeout(AlgorithmStats { rounds: round - 1, messages, dropped, newly_fixed }));
+        }
+
+        log_message!(logger, LogLevel::Debug, "\nStarting round {round}");
+
+        match options.schedule {
+            Schedule::Synchronous => {
+                // exchange color with all neighbors, every node pulls the colors of its neighbors
+                // permanent nodes never read their inbox, so they don't receive any messages
+                let colors: Vec<Coloring> = nodes.iter().map(|n| n.coloring).collect();
+
+                // which messages are lost is decided one node after another so it only depends on the seed,
+                // without dropping no random numbers are drawn so the colors are the same as before
+                let delivered: Vec<Vec<bool>> = if options.drop_prob > 0.0 {
+                    nodes.iter()
+                        .map(|n| match n.coloring {
+                            Candidate(_) => neighbors[n.id].iter().map(|_| !rng.gen_bool(options.drop_prob)).collect(),
+                            Permanent(_) => Vec::new(),
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                dropped += delivered.iter().flatten().filter(|&&d| !d).count();
+
+                // the inbox is only cleared when new messages arrive, so afterwards it still shows the last round of the node
+                for_each_node(nodes, |node| {
+                    if let Permanent(_) = node.coloring {
+                        return;
+                    }
+
+                    node.inbox.clear();
+                    for (i, &u) in neighbors[node.id].iter().enumerate() {
+                        if !delivered.is_empty() && !delivered[node.id][i] {
+                            log_message!(logger, LogLevel::Trace, "node {:3}: message to node {:3} was dropped", u, node.id);
+                            continue;
+                        }
+                        node.inbox.push(colors[u]);
+
+                        log_message!(logger, LogLevel::Trace, "node {:3}: sending to node {:3}:  {:?}", u, node.id, colors[u]);
+                    }
+                });
+                messages += nodes.iter().filter(|n| matches!(n.coloring, Candidate(_))).map(|n| n.inbox.len()).sum::<usize>();
+
+                // every node receives at most one message per neighbor each round
+                debug_assert!(nodes.iter().all(|n| n.inbox.len() <= neighbors[n.id].len()),
+                              "a node received more messages than it has neighbors");
+
+                // for all non permanent nodes compute available set of colors and permanently color if possible
+                // if not do next iteration and choose new random color
+                let mut candidates: Vec<(&mut Node, Option<Choices>)> = nodes.iter_mut()
+                    .filter(has_candidate_color)
+                    .map(|n| (n, None))
+                    .collect();
+
+                for_each_node(&mut candidates, |(node, choices)| {
+                    if node.inbox.len() < neighbors[node.id].len() {
+                        log_message!(logger, LogLevel::Trace, "node {:3} missed messages and keeps its color {:?}", node.id, node.coloring);
+                        return;
+                    }
+                    *choices = process_candidate(node, num_colors, logger);
+                });
+
+                // the new colors are chosen one node after another so they only depend on the seed
+                for (node, choices) in candidates {
+                    if let Some(choices) = choices {
+                        choose_new_color(node, &choices, options.color_bias, rng, logger);
+                    }
+                }
+            }
+            Schedule::Asynchronous => {
+                // the candidate nodes are processed one after another in a random order,
+                // each node reads the current colors of its neighbors, which may have changed in this round
+                let mut order: Vec<usize> = nodes.iter_mut()
+                    .filter(has_candidate_color)
+                    .map(|n| n.id)
+                    .collect();
+                order.shuffle(rng);
+
+                for v in order {
+                    let degree = neighbors[v].len();
+                    nodes[v].inbox.clear();
+                    if logger.is_some_and(|l| l.enabled(LogLevel::Trace)) {
+                        for &u in &neighbors[v] {
+                            log_message!(logger, LogLevel::Trace, "node {:3}: sending to node {:3}:  {:?}", u, v, nodes[u].coloring);
+                        }
+                    }
+
+                    // a node which missed a message can't decide anything in this round
+                    let lost = if options.drop_prob > 0.0 {
+                        (0..degree).filter(|_| rng.gen_bool(options.drop_prob)).count()
+                    } else {
+                        0
+                    };
+                    messages += degree - lost;
+                    dropped += lost;
+                    if lost > 0 {
+                        log_message!(logger, LogLevel::Trace, "node {:3} missed {lost} messages and keeps its color {:?}", v, nodes[v].coloring);
+                        continue;
+                    }
+                    for &u in &neighbors[v] {
+                        let coloring = nodes[u].coloring;
+                        nodes[v].inbox.push(coloring);
+                    }
+
+                    if let Some(choices) = process_candidate(&mut nodes[v], num_colors, logger) {
+                        choose_new_color(&mut nodes[v], &choices, options.color_bias, rng, logger);
+                    }
+                }
+            }
+        }
+
+        // count the nodes that went permanent in this round
+        let still_candidate = nodes.iter_mut().filter(has_candidate_color).count();
+        newly_fixed.push(remaining - still_candidate);
+        remaining = still_candidate;
+
+        // the reports are throttled, but the first and the last one are always printed
+        let report_due = match last_progress {
+            Some(t) => remaining == 0 || t.elapsed() >= PROGRESS_INTERVAL,
+            None => true,
+        };
+        if options.progress && report_due {
+            eprintln!("round {round}: {remaining} of {} nodes are still candidates", nodes.len());
+            last_progress = Some(Instant::now());
+        }
+
+        // check if the graph has a valid coloring
+        if remaining == 0 {
+            log_message!(logger, LogLevel::Info, "no candidate colors left, coloring should be fixed");
+            log_message!(logger, LogLevel::Info, "Finished after {round} rounds\n");
+            break;
+        }
+
+        if !options.priority_nodes.is_empty() && options.priority_nodes.iter().all(|&v| matches!(nodes[v].coloring, Permanent(_))) {
+            log_message!(logger, LogLevel::Info, "all priority nodes are permanent, {remaining} nodes are still candidates");
+            log_message!(logger, LogLevel::Info, "Finished after {round} rounds\n");
+            break;
+        }
+
+        // print new coloring
+        for node in nodes.iter_mut() {
+            log_message!(logger, LogLevel::Debug, "node {:3} has color {:?}", node.id, node.coloring);
+        }
+
+        if round >= options.max_rounds {
+            log_message!(logger, LogLevel::Info, "stopping after {round} rounds, the round limit was reached");
+            return Err(ColoringError::RoundLimit(AlgorithmStats { rounds: round, messages, dropped, newly_fixed }));
+        }
+
+        if options.step {
+            // stepping is pointless without seeing the coloring, unless the logger just printed it
+            if !logger.is_some_and(|l| l.enabled(LogLevel::Debug)) {
+                for node in nodes.iter() {
+                    eprintln!("node {:3} has color {:?}", node.id, node.coloring);
+                }
+            }
+            eprintln!("round {round} done, {remaining} candidates left, press Enter to continue");
+            // if stdin is closed there is nothing to wait for and the algorithm simply continues
+            let _ = io::stdin().read_line(&mut String::new());
+        }
+
+        round += 1;
+    }
+
+    Ok(AlgorithmStats { rounds: round, messages, dropped, newly_fixed })
+}
+
+/// colors the graph using the distributed randomized algorithm with a randomly seeded rng
+/// and returns the number of rounds taken, the messages sent and the final coloring
+/// uses the default options, so it gives up after `DEFAULT_MAX_ROUNDS` rounds
+pub fn color_graph(graph: &VecGraph, nodes: &mut [Node], delta: usize) -> Result<ColoringResult, ColoringError> {
+    let mut rng = StdRng::from_entropy();
+    let stats = distributed_randomized_coloring_algorithm(graph, nodes, delta + 1, &AlgorithmOptions::default(), &mut rng)?;
+
+    Ok(ColoringResult {
+        rounds: stats.rounds,
+        messages: stats.messages,
+        newly_fixed: stats.newly_fixed,
+        coloring: nodes.iter().map(|n| *n.coloring.color()).collect(),
+    })
+}
+
+/// colors the graph once for every seed with delta + 1 colors and the default options and returns the rounds of each run
+/// every run starts from a copy of `nodes`, so the runs are independent and `nodes` isn't changed
+/// a run which doesn't converge counts with the rounds after which it gave up, i.e. `DEFAULT_MAX_ROUNDS`
+pub fn round_distribution(graph: &VecGraph, nodes: &[Node], delta: usize, seeds: impl Iterator<Item=u64>) -> Vec<usize> {
+    seeds.map(|seed| {
+        let mut nodes = nodes.to_vec();
+        let mut rng = StdRng::seed_from_u64(seed);
+        match distributed_randomized_coloring_algorithm(graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut rng) {
+            Ok(stats) => stats.rounds,
+            Err(e) => e.stats().rounds,
+        }
+    }).collect()
+}
+
+/// returns all edges whose endpoints have the same color, the coloring is valid if there are none
+/// every edge is returned as `(u, v)` with `u < v`
+pub fn find_conflicts(graph: &VecGraph, nodes: &[Node]) -> Vec<(usize, usize)> {
+    graph.edges()
+        .map(|e| graph.enodes(e))
+        .map(|(u, v)| (u.index().min(v.index()), u.index().max(v.index())))
+        .filter(|(u, v)| nodes[*u].coloring.color() == nodes[*v].coloring.color())
+        .collect()
+}
+
+/// returns the number of rounds the algorithm is expected to take on a graph with `n` nodes and max degree `delta`
+/// when choosing from delta + 1 colors, this is the analysis by Luby and Johansson:
+/// in every round a candidate node keeps its color with probability at least 1/4,
+/// so after `r` rounds at most `n * (3/4)^r` candidates are expected to be left, which is less than one
+/// after `log_{4/3}(n)` rounds, the first round is counted too
+/// without edges every node keeps its color in the first round
+pub fn expected_rounds(n: usize, delta: usize) -> f64 {
+    if delta == 0 || n <= 1 {
+        return 1.0;
+    }
+
+    1.0 + (n as f64).ln() / (4.0f64 / 3.0).ln()
+}
+
+/// returns the number of distinct colors used by the nodes
+pub fn colors_used(nodes: &[Node]) -> usize {
+    nodes.iter().map(|n| *n.coloring.color()).collect::<HashSet<Color>>().len()
+}
+
+/// returns for every used color how many nodes have it, sorted by the number of nodes descending
+/// colors used by the same number of nodes are sorted by color
+pub fn color_histogram(nodes: &[Node]) -> Vec<(Color, usize)> {
+    let mut counts = BTreeMap::new();
+    for node in nodes {
+        *counts.entry(*node.coloring.color()).or_insert(0) += 1;
+    }
+
+    let mut histogram: Vec<(Color, usize)> = counts.into_iter().collect();
+    histogram.sort_by_key(|&(color, count)| (Reverse(count), color));
+    histogram
+}
+
+/// returns the degree of every vertex
+/// every undirected edge is only stored once, so it's counted for both of its endpoints
+pub fn degrees(graph: &VecGraph) -> Vec<usize> {
+    let mut degrees = vec![0; graph.num_nodes()];
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        degrees[u.index()] += 1;
+        degrees[v.index()] += 1;
+    }
+    degrees
+}
+
+/// returns the max degree (delta) of the graph, 0 for a graph without vertices
+pub fn max_degree(graph: &VecGraph) -> usize {
+    degrees(graph).into_iter().max().unwrap_or(0)
+}
+
+/// returns the size of a maximum clique, which is a lower bound for the number of colors any valid coloring needs
+/// uses a simple branch and bound search, this takes exponential time so it should only be used on small graphs
+pub fn clique_lower_bound(graph: &VecGraph) -> usize {
+    let n = graph.num_nodes();
+    let mut adjacent = vec![vec![false; n]; n];
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        adjacent[u.index()][v.index()] = true;
+        adjacent[v.index()][u.index()] = true;
+    }
+
+    /// extends the current clique of `size` vertices with the `candidates` adjacent to all of them
+    fn expand(adjacent: &[Vec<bool>], size: usize, candidates: &[usize], best: &mut usize) {
+        if size > *best {
+            *best = size;
+        }
+
+        for (i, &v) in candidates.iter().enumerate() {
+            // even adding all remaining candidates can't beat the best clique
+            if size + candidates.len() - i <= *best {
+                return;
+            }
+
+            let next: Vec<usize> = candidates[i + 1..].iter()
+                .copied()
+                .filter(|&u| adjacent[v][u])
+                .collect();
+            expand(adjacent, size + 1, &next, best);
+        }
+    }
+
+    let mut best = 0;
+    let candidates: Vec<usize> = (0..n).collect();
+    expand(&adjacent, 0, &candidates, &mut best);
+    best
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// the level of a diagnostic message, from the few messages about the whole run to a message for every node and neighbor
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// the start and end of the algorithm and the files written
+    Info,
+    /// every round and the colors of the nodes after it
+    Debug,
+    /// every message sent and every decision of a node
+    Trace,
+}
+
+/// the function the messages are passed to together with their level
+type Sink = dyn Fn(LogLevel, &str) + Send + Sync;
+
+/// receives the diagnostic messages up to a maximum level, e.g. to forward them to the logger of a host application
+/// cloning it is cheap, all clones share the same sink
+#[derive(Clone)]
+pub struct Logger {
+    max_level: LogLevel,
+    sink: Arc<Sink>,
+}
+
+impl Logger {
+    /// creates a logger which passes every message up to `max_level` to `sink`
+    pub fn new(max_level: LogLevel, sink: impl Fn(LogLevel, &str) + Send + Sync + 'static) -> Logger {
+        Logger {
+            max_level,
+            sink: Arc::new(sink),
+        }
+    }
+
+    /// creates a logger which prints every message up to `max_level` to stderr
+    pub fn stderr(max_level: LogLevel) -> Logger {
+        Logger::new(max_level, |_, message| eprintln!("{message}"))
+    }
+
+    /// returns whether messages of this level are passed to the sink
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        level <= self.max_level
+    }
+
+    /// passes the message to the sink if its level is enabled
+    pub fn log(&self, level: LogLevel, message: &str) {
+        if self.enabled(level) {
+            (self.sink)(level, message);
+        }
+    }
+}
+
+impl fmt::Debug for Logger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Logger").field("max_level", &self.max_level).finish_non_exhaustive()
+    }
+}
+
+/// logs a message formatted like `format!` with an `Option<&Logger>` at the given level,
+/// the message is only formatted if there is a logger and the level is enabled
+/// all diagnostic output of the library and the binary goes through here
+#[macro_export]
+macro_rules! log_message {
+    ($logger:expr, $level:expr, $($arg:tt)*) => {
+        if let Some(logger) = $logger {
+            if logger.enabled($level) {
+                logger.log($level, &format!($($arg)*));
+            }
+        }
+    };
+}
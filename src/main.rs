@@ -1,400 +1,1710 @@
-use std::collections::HashSet;
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::time::{Duration, Instant};
 
 use clap::{Parser, ValueEnum};
-use rand::{Rng, thread_rng};
-use rand::distributions::Uniform;
-use rand::prelude::IteratorRandom;
-use rs_graph::{Builder, VecGraph};
+use rand::{Rng, SeedableRng, thread_rng};
+use rand::rngs::StdRng;
+use rs_graph::VecGraph;
 use rs_graph::traits::{FiniteGraph, Indexable};
-use rs_graph::vecgraph::VecGraphBuilder;
 
-use crate::Coloring::{Candidate, Permanent};
+use color_reduction::{AlgorithmOptions, AlgorithmStats, clique_lower_bound, color_histogram, Coloring, ColoringError, colors_used, DEFAULT_MAX_ROUNDS, degrees, distributed_randomized_coloring_algorithm, expected_rounds, find_conflicts, InitialColoring, Node, Schedule};
+use color_reduction::generators::{barbell, caterpillar, chain, clustered, complement, complete_graph, cycle, grid, hydrocarbon, hypercube, generalized_petersen, kary_tree, king_grid, mycielski, petersen, random_graph, random_regular, torus, wheel, windmill};
+use color_reduction::input::{format_edge_list, graph_from_edge_pairs, load_adjacency_matrix, load_coloring, load_dimacs, load_dot, load_edge_list};
+use color_reduction::log_message;
+use color_reduction::logger::{LogLevel, Logger};
+use color_reduction::palette::{generate_colors, load_palette, palette_warnings};
+use color_reduction::sequential::{dsatur_coloring, greedy_coloring, recolor_reduce};
+
+/// exit codes telling scripts why a run failed, invalid input exits with 1 and clap uses 2 for invalid arguments
+const EXIT_INVALID_COLORING: i32 = 3;
+const EXIT_ROUND_LIMIT: i32 = 4;
+const EXIT_TIMEOUT: i32 = 5;
+const EXIT_TOO_MANY_COLORS: i32 = 6;
+const EXIT_NOT_DETERMINISTIC: i32 = 7;
 
-type Color = usize;
+/// this is the test case, it generates a complete graph with 200 vertices
+/// in such a case each color may only be used once
+/// we check this by sorting the nodes by color and checking that no two neighbors
+/// in the sorted vector have the same color, otherwise both nodes are reported
+fn test_case(cli: &Cli, rng: &mut impl Rng) {
+    let (graph, mut nodes, delta) = complete_graph(200);
+    // every undirected edge is stored once, so there is one edge for every pair of nodes
+    assert_eq!(graph.num_edges(), 200 * 199 / 2);
+    assert_eq!(delta, 199);
+    check_node_options(&graph, delta + 1, cli);
+    let stats = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &algorithm_options(cli), rng)
+        .unwrap_or_else(|e| panic!("Algorithm did not converge: {e}"));
+
+    println!("\n\nAlgorithm finished:");
+    println!("Converged after {} rounds", stats.rounds);
+    println!("Total messages: {}", stats.messages);
+    for node in nodes.iter_mut() {
+        println!("node {:3} has permanent color {:3}", node.id, node.coloring.color());
+    }
+
+    print_validation(&graph, &nodes, 0);
+    println!("Used {} of {} colors", colors_used(&nodes), delta + 1);
+
+    // in a complete graph, each color must only be used once
+    // ties are broken by id so nodes sharing a color are always reported in the same order
+    nodes.sort_by_key(|n| (*n.coloring.color(), n.id));
+    println!("\nSorting by color:");
+    for node in nodes.iter_mut() {
+        println!("node {:3} has permanent color {:3}", node.id, node.coloring.color());
+    }
+
+    // after sorting, nodes sharing a color are next to each other
+    if let Some(pair) = nodes.windows(2).find(|pair| pair[0].coloring.color() == pair[1].coloring.color()) {
+        panic!("node {} and node {} both have color {}", pair[0].id, pair[1].id, pair[0].coloring.color());
+    }
+}
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Run mode
+    #[arg(short, long, value_enum, default_value_t = RunMode::Testcase)]
+    mode: RunMode,
+
+    /// Number of nodes to be used by the run modes whose size isn't given by their own options or an input file
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser ! (u64).range(1..))]
+    num: u64,
+
+    /// Print additional information while running the algorithm to stderr
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// How much information is printed with verbose
+    #[arg(long, value_enum, default_value_t = LogLevelArg::Trace, requires = "verbose")]
+    log_level: LogLevelArg,
+
+    /// Print the number of remaining candidate nodes to stderr after each round, at most every 100ms
+    #[arg(long)]
+    progress: bool,
+
+    /// Wait for Enter after every round of the distributed algorithm to step through it, requires verbose
+    #[arg(long, requires = "verbose", conflicts_with_all = ["trials", "repeat_until_optimal"])]
+    step: bool,
+
+    /// Print how many nodes have each color, sorted by the number of nodes
+    #[arg(long)]
+    histogram: bool,
+
+    /// Print node ids starting at 1 instead of 0, this only changes the output and not how nodes are numbered
+    /// in input files or with --edge
+    #[arg(long)]
+    one_indexed: bool,
+
+    /// Color the complement of the graph, i.e. nodes are adjacent exactly if they aren't adjacent in the graph
+    #[arg(long)]
+    complement: bool,
+
+    /// Color the complete graph, chain, hydrocarbon, cycle, wheel, tree and random graph with the given number of nodes
+    /// and print a table comparing their rounds and colors used, the run mode is ignored
+    #[arg(long)]
+    compare_modes: bool,
+
+    /// Color the graph of the run mode with each of the given numbers of nodes using the same seed
+    /// and print `size,rounds,colors,ms` as CSV, verbose output is suppressed during the sweep
+    #[arg(long, value_delimiter = ',', num_args = 1.., conflicts_with_all = ["compare_modes", "step"], value_parser = clap::value_parser ! (u64).range(1..))]
+    scaling_sweep: Vec<u64>,
+
+    /// Don't color the graph but check the coloring given with --coloring, e.g. to grade a submission
+    #[arg(long, requires = "coloring")]
+    validate_only: bool,
+
+    /// CSV file with one `id,color` pair per line like the output of `-f csv`, checked with --validate-only
+    /// the ids start at 1 with --one-indexed
+    #[arg(long, requires = "validate_only")]
+    coloring: Option<String>,
+
+    /// Color the graph twice with the same seed and check that both colorings are the same instead of printing one
+    #[arg(long, conflicts_with = "step")]
+    check_determinism: bool,
+
+    /// Only create the graph and print its number of vertices, edges and degrees without coloring it
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Print the degrees of all nodes sorted from largest to smallest, to stderr unless the format is human
+    /// or it's a dry run
+    #[arg(long)]
+    degree_sequence: bool,
+
+    /// Only print the color of each node, nothing is printed to stdout if an output file is given
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Create a dot file of the graph to visualize with graphviz, has no effect for testcase run mode
+    #[arg(short, long)]
+    dotfile: Option<String>,
+
+    /// Add the graph as a new subgraph to the dot file instead of overwriting it, to collect several runs in one file
+    #[arg(long, requires = "dotfile")]
+    append: bool,
+
+    /// Size of the grid as ROWSxCOLS, required for grid, king-grid and torus run mode
+    #[arg(short, long, value_parser = parse_grid_size, required_if_eq_any([("mode", "grid"), ("mode", "king-grid"), ("mode", "torus")]))]
+    grid: Option<(usize, usize)>,
+
+    /// Dimension of the hypercube, required for hypercube run mode
+    #[arg(long, required_if_eq("mode", "hypercube"), value_parser = clap::value_parser ! (u64).range(..=20))]
+    dimension: Option<u64>,
+
+    /// Number of outer vertices n of the generalized Petersen graph GP(n, k), required for generalized-petersen run mode
+    #[arg(long, required_if_eq("mode", "generalized-petersen"), value_parser = clap::value_parser ! (u64).range(3..))]
+    outer: Option<u64>,
+
+    /// Step k between the inner vertices of the generalized Petersen graph GP(n, k), required for generalized-petersen run mode
+    #[arg(long, required_if_eq("mode", "generalized-petersen"), value_parser = clap::value_parser ! (u64).range(1..))]
+    inner_step: Option<u64>,
+
+    /// Number of vertices of each of the two cliques of the barbell graph, required for barbell run mode
+    #[arg(long, required_if_eq("mode", "barbell"), value_parser = clap::value_parser ! (u64).range(1..))]
+    clique_size: Option<u64>,
+
+    /// Number of vertices of the path between the two cliques of the barbell graph
+    #[arg(long, default_value_t = 5)]
+    path_len: u64,
+
+    /// Number of triangles sharing the hub of the windmill graph, required for windmill run mode
+    #[arg(long, required_if_eq("mode", "windmill"), value_parser = clap::value_parser ! (u64).range(1..))]
+    triangles: Option<u64>,
+
+    /// Chromatic number k of the Mycielski graph M_k, required for mycielski run mode
+    #[arg(long, required_if_eq("mode", "mycielski"), value_parser = clap::value_parser ! (u64).range(1..=14))]
+    chromatic_number: Option<u64>,
+
+    /// Number of spine vertices of the caterpillar, required for caterpillar run mode
+    #[arg(long, required_if_eq("mode", "caterpillar"), value_parser = clap::value_parser ! (u64).range(1..))]
+    spine: Option<u64>,
+
+    /// Number of leaves attached to each spine vertex for caterpillar run mode
+    #[arg(long, default_value_t = 2)]
+    leaves: u64,
+
+    /// An edge given as `U,V`, can be repeated to build a small graph, required for edges run mode
+    #[arg(long = "edge", value_name = "U,V", value_parser = parse_edge, required_if_eq("mode", "edges"))]
+    edges: Vec<(usize, usize)>,
+
+    /// Maximum number of rounds after which the algorithm gives up
+    #[arg(long, default_value_t = DEFAULT_MAX_ROUNDS as u64, value_parser = clap::value_parser ! (u64).range(1..))]
+    max_rounds: u64,
+
+    /// Time in milliseconds after which the algorithm gives up, checked at the start of every round
+    #[arg(long)]
+    timeout_ms: Option<u64>,
+
+    /// Fix nodes to a permanent color before the coloring starts, given as ID=COLOR, e.g. `--precolor 3=0 7=1`
+    #[arg(long, value_parser = parse_precolor, num_args = 1..)]
+    precolor: Vec<(usize, usize)>,
+
+    /// Comma separated ids of nodes, the distributed algorithm stops as soon as all of them have a permanent color
+    #[arg(long, value_delimiter = ',')]
+    priority_nodes: Vec<usize>,
+
+    /// Probability of each edge being present for random run mode
+    #[arg(short, long, default_value_t = 0.5, value_parser = parse_probability)]
+    probability: f64,
+
+    /// Probability smaller than 1 with which a node choosing a new color takes the smallest one not used by its neighbors
+    /// instead of a random one, the number of colors used without the bias is printed for comparison
+    #[arg(long, default_value_t = 0.0, value_parser = parse_color_bias)]
+    color_bias: f64,
+
+    /// Probability with which each message of the distributed algorithm is lost, a node missing a message
+    /// keeps its color for the round, the rounds needed without lost messages are printed for comparison
+    #[arg(long, default_value_t = 0.0, value_parser = parse_probability)]
+    drop_prob: f64,
+
+    /// Print a warning and exit with an error if the final coloring uses more than this many colors,
+    /// e.g. to detect a regression in the coloring quality in CI
+    #[arg(long)]
+    warn_if_colors_exceed: Option<u64>,
+
+    /// Number of times the graph is colored, trial `i` uses the seed plus `i`
+    /// statistics over all trials are printed but only the last trial is printed in detail
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser ! (u64).range(1..))]
+    trials: u64,
+
+    /// Color the graph again with the seed plus 1, 2, ... until the coloring uses as many colors as the graph needs,
+    /// only for run modes whose chromatic number is known
+    #[arg(long, conflicts_with = "trials")]
+    repeat_until_optimal: bool,
+
+    /// Maximum number of attempts for --repeat-until-optimal
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser ! (u64).range(1..))]
+    max_attempts: u64,
+
+    /// Number of colors the distributed algorithm chooses from instead of delta + 1,
+    /// with fewer colors than the graph needs it never converges and stops after the maximum number of rounds
+    #[arg(short, long, value_parser = clap::value_parser ! (u64).range(1..))]
+    colors: Option<u64>,
+
+    /// After the coloring move every node to the smallest color used elsewhere that's free among its neighbors,
+    /// this never needs more colors and often fewer
+    #[arg(long)]
+    reduce: bool,
+
+    /// Coloring algorithm to use, has no effect for testcase run mode
+    #[arg(short, long, value_enum, default_value_t = Algorithm::Distributed)]
+    algorithm: Algorithm,
+
+    /// Scheduling of the nodes within a round of the distributed algorithm
+    #[arg(long, value_enum, default_value_t = ScheduleArg::Sync)]
+    schedule: ScheduleArg,
+
+    /// Colors of the nodes in the first round of the distributed algorithm
+    #[arg(long, value_enum, default_value_t = InitArg::Random)]
+    init: InitArg,
+
+    /// Output format of the result, has no effect for testcase run mode
+    #[arg(short, long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+
+    /// Number of clusters for clustered run mode
+    #[arg(long, required_if_eq("mode", "clustered"), value_parser = clap::value_parser ! (u64).range(1..))]
+    clusters: Option<u64>,
+
+    /// Number of nodes in each cluster for clustered run mode
+    #[arg(long, required_if_eq("mode", "clustered"), value_parser = clap::value_parser ! (u64).range(1..))]
+    cluster_size: Option<u64>,
+
+    /// Number of random edges between different clusters for clustered run mode
+    #[arg(long, default_value_t = 10)]
+    inter_edges: u64,
+
+    /// Degree of every node, required for random-regular run mode
+    #[arg(long, required_if_eq("mode", "random-regular"))]
+    degree: Option<u64>,
+
+    /// Maximum number of children of each node for tree run mode
+    #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser ! (u64).range(1..))]
+    branching: u64,
+
+    /// File to write the color of each node into instead of stdout, with json format the whole result is written
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Graph file to read, an edge list for file run mode, a DIMACS .col file for dimacs run mode
+    /// a dot file for dot run mode or an adjacency matrix for matrix run mode, `-` reads from stdin
+    #[arg(short, long, required_if_eq_any([("mode", "file"), ("mode", "dimacs"), ("mode", "dot"), ("mode", "matrix")]))]
+    input: Option<String>,
+
+    /// Write the edges of the graph into an edge list file which can be loaded again using the file run mode
+    #[arg(long)]
+    export_edges: Option<String>,
+
+    /// Create a GraphML file of the graph for tools like yEd or Gephi, has no effect for testcase run mode
+    #[arg(long)]
+    graphml: Option<String>,
+
+    /// Create an SVG image of the graph with the vertices on a circle, has no effect for testcase run mode
+    #[arg(long)]
+    svg: Option<String>,
+
+    /// Write the final state of every node, its id, coloring and the colors its inbox received in its last round, as JSON into this file,
+    /// it's also written if the algorithm didn't converge
+    #[arg(long)]
+    dump_state: Option<String>,
+
+    /// Label the nodes in the dot file with their id and color
+    #[arg(short, long)]
+    labels: bool,
+
+    /// Palette file with one color per line used for the dot file, missing colors are generated
+    #[arg(long)]
+    palette: Option<String>,
+
+    /// Built-in colorblind-safe palette used for the dot file, missing colors are generated
+    #[arg(long, value_enum, conflicts_with = "palette")]
+    palette_scheme: Option<PaletteScheme>,
+
+    /// Generate random colors for the dot file instead of evenly spaced hues
+    #[arg(long)]
+    random_palette: bool,
+
+    /// Graphs with at most this many nodes get a lower bound for the number of colors from a maximum clique,
+    /// the search takes exponential time so bigger graphs are skipped
+    #[arg(long, default_value_t = 64)]
+    clique_limit: u64,
 
-#[derive(Copy, Clone, Debug)]
-enum Coloring {
-    Permanent(Color),
-    Candidate(Color),
+    /// Seed for the random number generator, a random seed is chosen and printed if not specified
+    #[arg(short, long)]
+    seed: Option<u64>,
+}
+
+/// parses a grid size given as `ROWSxCOLS`, e.g. `4x5`
+fn parse_grid_size(s: &str) -> Result<(usize, usize), String> {
+    let (rows, cols) = s.split_once('x')
+        .ok_or_else(|| format!("'{s}' is not of the form ROWSxCOLS"))?;
+    let rows: usize = rows.trim().parse().map_err(|e| format!("invalid number of rows '{rows}': {e}"))?;
+    let cols: usize = cols.trim().parse().map_err(|e| format!("invalid number of columns '{cols}': {e}"))?;
+
+    if rows == 0 || cols == 0 {
+        return Err("the grid must have at least one row and one column".to_string());
+    }
+
+    Ok((rows, cols))
+}
+
+/// parses an edge given as `U,V`, e.g. `0,1`
+fn parse_edge(s: &str) -> Result<(usize, usize), String> {
+    let (u, v) = s.split_once(',')
+        .ok_or_else(|| format!("'{s}' is not of the form U,V"))?;
+    let u: usize = u.trim().parse().map_err(|e| format!("invalid vertex '{u}': {e}"))?;
+    let v: usize = v.trim().parse().map_err(|e| format!("invalid vertex '{v}': {e}"))?;
+
+    Ok((u, v))
+}
+
+/// parses a precolored node given as `ID=COLOR`, e.g. `3=0`
+fn parse_precolor(s: &str) -> Result<(usize, usize), String> {
+    let (id, color) = s.split_once('=')
+        .ok_or_else(|| format!("'{s}' is not of the form ID=COLOR"))?;
+    let id: usize = id.trim().parse().map_err(|e| format!("invalid node '{id}': {e}"))?;
+    let color: usize = color.trim().parse().map_err(|e| format!("invalid color '{color}': {e}"))?;
+
+    Ok((id, color))
+}
+
+/// parses a probability which must be in the range [0, 1]
+fn parse_probability(s: &str) -> Result<f64, String> {
+    let p: f64 = s.parse().map_err(|e| format!("invalid probability '{s}': {e}"))?;
+
+    if !(0.0..=1.0).contains(&p) {
+        return Err(format!("the probability {p} is not in the range [0, 1]"));
+    }
+
+    Ok(p)
+}
+
+/// parses the color bias, which must be smaller than 1 since with a bias of 1 two neighbors
+/// can keep choosing the same smallest free color and the algorithm never converges
+fn parse_color_bias(s: &str) -> Result<f64, String> {
+    let p = parse_probability(s)?;
+
+    if p >= 1.0 {
+        return Err(format!("the color bias {p} must be smaller than 1, otherwise neighbors can keep choosing the same color"));
+    }
+
+    Ok(p)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Algorithm {
+    /// The distributed randomized coloring algorithm
+    Distributed,
+    /// Sequential greedy coloring in the order of the node ids
+    Greedy,
+    /// Sequential DSATUR heuristic coloring the most saturated node first
+    Dsatur,
 }
 
-impl Coloring {
-    fn color(&self) -> &Color {
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum ScheduleArg {
+    /// All nodes exchange their colors and then all nodes update
+    Sync,
+    /// Nodes update one after another in a random order seeing the current colors of their neighbors
+    Async,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum InitArg {
+    /// Every node chooses a random color in the first round
+    Random,
+    /// Node i starts with color i modulo the number of colors
+    Deterministic,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum LogLevelArg {
+    /// The start and end of the algorithm and the files written
+    Info,
+    /// Every round and the colors of the nodes after it
+    Debug,
+    /// Every message sent and every decision of a node
+    Trace,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Format {
+    /// Human readable output
+    Human,
+    /// A single JSON object containing statistics and the coloring
+    Json,
+    /// The color of every node as CSV with the header `id,color`
+    Csv,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum PaletteScheme {
+    /// The 8 colors by Okabe and Ito
+    OkabeIto,
+    /// The 7 bright colors by Paul Tol
+    Tol,
+}
+
+impl PaletteScheme {
+    /// returns the colors of the palette
+    fn colors(self) -> &'static [&'static str] {
         match self {
-            Permanent(v) => { v }
-            Candidate(v) => { v }
+            PaletteScheme::OkabeIto => &["#e69f00", "#56b4e9", "#009e73", "#f0e442", "#0072b2", "#d55e00", "#cc79a7", "#000000"],
+            PaletteScheme::Tol => &["#4477aa", "#ee6677", "#228833", "#ccbb44", "#66ccee", "#aa3377", "#bbbbbb"],
         }
     }
 }
 
-#[derive(Clone, Debug)]
-struct Node {
-    id: usize,
-    coloring: Coloring,
-    inbox: Vec<Coloring>,
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum RunMode {
+    Testcase,
+    Verify,
+    CompleteGraph,
+    Chain,
+    Hydrocarbon,
+    Cycle,
+    Grid,
+    KingGrid,
+    Torus,
+    File,
+    Dimacs,
+    Dot,
+    Matrix,
+    Edges,
+    Random,
+    RandomRegular,
+    Clustered,
+    Wheel,
+    Windmill,
+    Barbell,
+    Tree,
+    Petersen,
+    GeneralizedPetersen,
+    Hypercube,
+    Caterpillar,
+    Mycielski,
 }
 
-fn N(id: usize) -> Node {
-    Node {
-        id,
-        coloring: Candidate(id),
-        inbox: Vec::new(),
-    }
+/// options controlling how the dot file is written
+struct DotOptions {
+    /// whether each node is labeled with its id and color
+    labels: bool,
+    /// whether the graph is added to the graphs already in the file instead of replacing them
+    append: bool,
+    /// added to the node ids in the labels, the node names stay 0-indexed so the file can be loaded again
+    id_offset: usize,
 }
 
-/// creates a complete graph with `num_nodes` vertices
-/// the graph has max degree `num_nodes`
-/// returns the graph, a vector of nodes and delta (max degree)
-fn complete_graph(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
-    let mut nodes = Vec::with_capacity(num_nodes);
-    let mut g = VecGraphBuilder::new();
-    let g_nodes = g.add_nodes(num_nodes);
-
-    for n1 in &g_nodes {
-        for n2 in &g_nodes {
-            if n1 != n2 {
-                g.add_edge(*n1, *n2);
-            }
+/// writes the graph into a dot file, the nodes are filled with the color of their color index
+/// with `options.append` the graph is added as a subgraph `cluster_runK` to the graph already in the file,
+/// where `K` counts the appended runs, its nodes are named `rK_nX` so they don't collide with the other runs,
+/// an error is returned without changing the file if it isn't empty and doesn't end with the closing brace of a graph
+fn graph_to_dot(file_path: &str, graph: &VecGraph, nodes: &[Node], unique_colors: &[String], options: &DotOptions, logger: Option<&Logger>) -> Result<(), String> {
+    log_message!(logger, LogLevel::Info, "Writing dot file into '{}'", file_path);
+
+    // when appending the closing brace of the existing graph is removed and written again after the new subgraph,
+    // a missing or empty file is started like a new one
+    let existing = if options.append {
+        fs::read_to_string(file_path).unwrap_or_default()
+    } else {
+        String::new()
+    };
+    let keep = if existing.trim().is_empty() {
+        0
+    } else if existing.trim_end().ends_with('}') {
+        existing.rfind('}').unwrap()
+    } else {
+        return Err(format!("Appending to dot file '{file_path}' failed: it doesn't end with the closing '}}' of a graph"));
+    };
+    let run = existing.matches("subgraph cluster_run").count();
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(!options.append)
+        .open(file_path);
+
+    if file.is_err() {
+        panic!("Writing dot file failed: {:?}", file.err().unwrap());
+    }
+
+    let mut file = file.unwrap();
+    let prefix = if options.append {
+        file.set_len(keep as u64).unwrap();
+        file.seek(SeekFrom::End(0)).unwrap();
+        if keep == 0 {
+            file.write_all("strict graph {\n".as_bytes()).unwrap();
         }
-        nodes.push(N(n1.index()));
+        file.write_all(format!("subgraph cluster_run{run} {{\nlabel=\"run {run}\"\n").as_bytes()).unwrap();
+        format!("r{run}_n")
+    } else {
+        file.write_all("strict graph {\n".as_bytes()).unwrap();
+        "n".to_string()
+    };
+
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        file.write_all(format!("{prefix}{} -- {prefix}{}\n", u.index(), v.index()).as_bytes()).unwrap();
+    }
+
+    for (id, node) in nodes.iter().enumerate() {
+        let color = &unique_colors[*node.coloring.color()];
+        let label = if options.labels {
+            format!(", label=\"n{}\\ncolor {}\"", id + options.id_offset, node.coloring.color())
+        } else {
+            String::new()
+        };
+        file.write_all(format!("{prefix}{} [color=\"black\", fillcolor=\"{}\", style=filled{}]\n", id, color, label).as_bytes()).unwrap();
     }
 
-    let delta = num_nodes - 1;
-    (g.into_graph(), nodes, delta)
+    if options.append {
+        file.write_all("}\n".as_bytes()).unwrap();
+    }
+    file.write_all("}\n".as_bytes()).unwrap();
+    file.flush().unwrap();
+    Ok(())
 }
 
-/// creates a graph that is a chain of vertices with `num_nodes` vertices
-/// the graph has max degree 2
-/// returns the graph, a vector of nodes and delta (max degree)
-fn chain(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
-    let mut nodes = Vec::with_capacity(num_nodes);
-    let mut g = VecGraphBuilder::new();
+/// writes the graph into a GraphML file, each node has its color and color index as data attributes
+fn graph_to_graphml(file_path: &str, graph: &VecGraph, nodes: &[Node], unique_colors: &[String], logger: Option<&Logger>) {
+    log_message!(logger, LogLevel::Info, "Writing GraphML file into '{}'", file_path);
 
-    let g_nodes = g.add_nodes(num_nodes);
+    let file = File::create(file_path);
+    if file.is_err() {
+        panic!("Writing GraphML file failed: {:?}", file.err().unwrap());
+    }
 
-    for n in &g_nodes {
-        nodes.push(N(n.index()));
+    let mut file = BufWriter::new(file.unwrap());
+    file.write_all(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"color\" for=\"node\" attr.name=\"color\" attr.type=\"string\"/>\n",
+        "  <key id=\"color_index\" for=\"node\" attr.name=\"color_index\" attr.type=\"int\"/>\n",
+        "  <graph id=\"G\" edgedefault=\"undirected\">\n",
+    ).as_bytes()).unwrap();
+
+    for node in nodes {
+        let color = node.coloring.color();
+        file.write_all(format!("    <node id=\"n{}\"><data key=\"color\">{}</data><data key=\"color_index\">{}</data></node>\n",
+                               node.id, unique_colors[*color], color).as_bytes()).unwrap();
     }
 
-    for i in 0..g_nodes.len() - 1 {
-        g.add_edge(g_nodes[i], g_nodes[i + 1]);
-        g.add_edge(g_nodes[i + 1], g_nodes[i]);
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        file.write_all(format!("    <edge source=\"n{}\" target=\"n{}\"/>\n", u.index(), v.index()).as_bytes()).unwrap();
     }
 
-    (g.into_graph(), nodes, (num_nodes - 1).min(2))
+    file.write_all("  </graph>\n</graphml>\n".as_bytes()).unwrap();
+    file.flush().unwrap();
 }
 
-/// creates a graph that is similar to hydrocarbon chains
-/// it will try to make the chain as long as possible using `num_nodes` nodes
-/// it there aren't enough nodes some carbon atoms will not have all hydrogen neighbors
-/// the graph has max degree 4
-/// returns the graph, a vector of nodes and delta (max degree)
-fn hydrocarbon(num_nodes: usize) -> (VecGraph, Vec<Node>, usize) {
-    let mut nodes = Vec::with_capacity(num_nodes);
-    let mut g = VecGraphBuilder::new();
+/// writes the graph into an SVG file so it can be viewed without graphviz
+/// the vertices are placed evenly on a circle and filled with the color of their color index
+/// `id_offset` is added to the node ids shown in the tooltips
+fn graph_to_svg(file_path: &str, graph: &VecGraph, nodes: &[Node], unique_colors: &[String], id_offset: usize, logger: Option<&Logger>) {
+    log_message!(logger, LogLevel::Info, "Writing SVG file into '{}'", file_path);
 
-    let g_nodes = g.add_nodes(num_nodes);
-    for n in &g_nodes {
-        nodes.push(N(n.index()));
+    let file = File::create(file_path);
+    if file.is_err() {
+        panic!("Writing SVG file failed: {:?}", file.err().unwrap());
     }
 
-    let mut node_counter = 0;
+    // the circle grows with the number of vertices so neighboring vertices don't overlap
+    const NODE_RADIUS: f64 = 10.0;
+    const MARGIN: f64 = 2.0 * NODE_RADIUS;
+    let radius = (nodes.len() as f64 * 3.0 * NODE_RADIUS / std::f64::consts::TAU).max(100.0);
+    let center = radius + MARGIN;
+    let position = |id: usize| {
+        let angle = std::f64::consts::TAU * id as f64 / nodes.len() as f64;
+        (center + radius * angle.cos(), center + radius * angle.sin())
+    };
+
+    let mut file = BufWriter::new(file.unwrap());
+    let size = 2.0 * center;
+    file.write_all(format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size:.0}\" height=\"{size:.0}\" viewBox=\"0 0 {size:.0} {size:.0}\">\n").as_bytes()).unwrap();
+
+    // the edges are drawn first so they are below the vertices
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        let (x1, y1) = position(u.index());
+        let (x2, y2) = position(v.index());
+        file.write_all(format!("  <line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\" stroke=\"black\"/>\n").as_bytes()).unwrap();
+    }
 
-    // add first hydrogen [0] and carbon [1] bond
-    if num_nodes >= 2 {
-        g.add_edge(g_nodes[0], g_nodes[1]);
-        g.add_edge(g_nodes[1], g_nodes[0]);
+    for node in nodes {
+        let (x, y) = position(node.id);
+        let color = node.coloring.color();
+        file.write_all(format!("  <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"{NODE_RADIUS}\" fill=\"{}\" stroke=\"black\"><title>n{} color {}</title></circle>\n",
+                               unique_colors[*color], node.id + id_offset, color).as_bytes()).unwrap();
     }
-    node_counter += 2;
-    let mut last_carbon = 1;
 
-    loop {
-        if node_counter >= num_nodes {
-            break;
-        }
+    file.write_all("</svg>\n".as_bytes()).unwrap();
+    file.flush().unwrap();
+}
+
+/// returns a coloring as a JSON object like `{"state":"permanent","color":3}`
+fn coloring_json(coloring: &Coloring) -> String {
+    let state = match coloring {
+        Coloring::Permanent(_) => "permanent",
+        Coloring::Candidate(_) => "candidate",
+    };
+    format!("{{\"state\":\"{state}\",\"color\":{}}}", coloring.color())
+}
 
-        let top_hydrogen = last_carbon + 1;
-        let bottom_hydrogen = last_carbon + 2;
-        let next_carbon = last_carbon + 3;
+/// writes the state of all nodes as a JSON array into a file, one node per line so states can be diffed
+fn dump_state(file_path: &str, nodes: &[Node], logger: Option<&Logger>) {
+    log_message!(logger, LogLevel::Info, "Writing node state into '{}'", file_path);
 
-        // add top hydrogen to last carbon
-        g.add_edge(g_nodes[last_carbon], g_nodes[top_hydrogen]);
-        g.add_edge(g_nodes[top_hydrogen], g_nodes[last_carbon]);
+    let file = File::create(file_path).unwrap_or_else(|e| panic!("Writing node state failed: {e:?}"));
+    let mut file = BufWriter::new(file);
 
-        node_counter += 1;
-        if node_counter == num_nodes {
-            break;
-        }
+    file.write_all("[\n".as_bytes()).unwrap();
+    for (i, node) in nodes.iter().enumerate() {
+        let colors: Vec<String> = node.inbox().colors().map(|c| c.to_string()).collect();
+        let permanent: Vec<String> = node.inbox().permanent_colors().map(|c| c.to_string()).collect();
+        let separator = if i + 1 < nodes.len() { "," } else { "" };
+        file.write_all(format!("{{\"id\":{},\"coloring\":{},\"inbox\":{{\"messages\":{},\"colors\":[{}],\"permanent\":[{}]}}}}{separator}\n",
+                               node.id, coloring_json(&node.coloring), node.inbox().len(), colors.join(","), permanent.join(",")).as_bytes()).unwrap();
+    }
+    file.write_all("]\n".as_bytes()).unwrap();
+    file.flush().unwrap();
+}
 
-        // add bottom hydrogen to last carbon
-        g.add_edge(g_nodes[last_carbon], g_nodes[bottom_hydrogen]);
-        g.add_edge(g_nodes[bottom_hydrogen], g_nodes[last_carbon]);
+/// prints whether the coloring is valid, if not all conflicting edges are printed
+/// `id_offset` is added to the printed node ids
+fn print_validation(graph: &VecGraph, nodes: &[Node], id_offset: usize) {
+    let conflicts = find_conflicts(graph, nodes);
+    if conflicts.is_empty() {
+        println!("VALID");
+        return;
+    }
 
-        node_counter += 1;
-        if node_counter == num_nodes {
-            break;
-        }
+    println!("INVALID: {} edges connect nodes with the same color", conflicts.len());
+    for (u, v) in conflicts {
+        println!("node {} and node {} are adjacent and both have color {}", u + id_offset, v + id_offset, nodes[u].coloring.color());
+    }
+}
 
-        // add new carbon or last hydrogen
-        g.add_edge(g_nodes[last_carbon], g_nodes[next_carbon]);
-        g.add_edge(g_nodes[next_carbon], g_nodes[last_carbon]);
-        last_carbon = next_carbon;
+/// prints how many nodes got a permanent color in each round and how many are left as CSV
+fn print_convergence(num_nodes: usize, stats: &AlgorithmStats) {
+    println!("round,newly_fixed,remaining");
+    let mut remaining = num_nodes;
+    for (i, fixed) in stats.newly_fixed.iter().enumerate() {
+        remaining -= fixed;
+        println!("{},{},{}", i + 1, fixed, remaining);
+    }
+}
 
-        node_counter += 1;
-        if node_counter == num_nodes {
-            break;
+/// minimum, maximum, mean and standard deviation of a value over multiple trials
+struct Summary {
+    min: usize,
+    max: usize,
+    mean: f64,
+    stddev: f64,
+}
+
+impl Summary {
+    /// summarizes the values, returns `None` if there are no values
+    fn of(values: &[usize]) -> Option<Summary> {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<usize>() as f64 / n;
+        let variance = values.iter().map(|&v| (v as f64 - mean).powi(2)).sum::<f64>() / n;
+
+        Some(Summary {
+            min: *values.iter().min()?,
+            max: *values.iter().max()?,
+            mean,
+            stddev: variance.sqrt(),
+        })
+    }
+
+    fn to_json(&self) -> String {
+        format!("{{\"min\":{},\"max\":{},\"mean\":{},\"stddev\":{}}}", self.min, self.max, self.mean, self.stddev)
+    }
+}
+
+/// statistics of the rounds and colors used over all trials which converged
+struct TrialSummary {
+    trials: u64,
+    failed: usize,
+    rounds: Option<Summary>,
+    colors_used: Option<Summary>,
+}
+
+/// everything printed about a run of the algorithm in addition to the coloring
+struct Report {
+    /// number of colors the nodes could choose from
+    num_colors: usize,
+    result: Result<AlgorithmStats, ColoringError>,
+    /// size of a maximum clique, `None` if the graph has more nodes than the clique limit
+    lower_bound: Option<usize>,
+    /// `None` if the chromatic number of the graph isn't known
+    chromatic_number: Option<usize>,
+    /// colors used by the same run without the color bias, `None` if no bias is used
+    unbiased_colors: Option<usize>,
+    /// `None` if only a single trial was run
+    trials: Option<TrialSummary>,
+    /// number of attempts made with --repeat-until-optimal, the last one is printed
+    attempts: Option<u64>,
+    /// rounds predicted by the analysis, `None` if it doesn't apply because not delta + 1 colors are used
+    expected_rounds: Option<f64>,
+    /// colors used before the --reduce pass, `None` if it didn't run
+    colors_before_reduce: Option<usize>,
+    /// rounds needed by the same run without lost messages, `None` if no messages are dropped
+    rounds_without_drops: Option<usize>,
+}
+
+/// prints the result of the algorithm as a single JSON object
+/// the coloring is written into `out`
+fn print_json(out: &mut dyn Write, cli: &Cli, graph: &VecGraph, nodes: &[Node], delta: usize, report: &Report) {
+    let id_offset = usize::from(cli.one_indexed);
+    let (status, stats) = match &report.result {
+        Ok(stats) => ("converged", stats),
+        Err(e @ ColoringError::RoundLimit(_)) => ("round_limit", e.stats()),
+        Err(e @ ColoringError::Timeout(_)) => ("timeout", e.stats()),
+        Err(e @ ColoringError::InvalidOptions(_)) => ("invalid_options", e.stats()),
+    };
+    let conflicts = find_conflicts(graph, nodes);
+    let histogram = color_histogram(nodes).iter()
+        .map(|(color, count)| JsonObject::new().field("color", color).field("count", count).to_string())
+        .collect::<Vec<_>>();
+    let trials = report.trials.as_ref().map(|t| JsonObject::new()
+        .field("count", t.trials)
+        .field("failed", t.failed)
+        .optional("rounds", t.rounds.as_ref().map(Summary::to_json))
+        .optional("colors_used", t.colors_used.as_ref().map(Summary::to_json)));
+    let coloring = nodes.iter()
+        .map(|n| JsonObject::new().field("id", n.id + id_offset).field("color", n.coloring.color()).to_string())
+        .collect::<Vec<_>>();
+
+    let json = JsonObject::new()
+        .string("mode", cli.mode.to_possible_value().unwrap().get_name())
+        .string("algorithm", cli.algorithm.to_possible_value().unwrap().get_name())
+        .string("schedule", cli.schedule.to_possible_value().unwrap().get_name())
+        .field("seed", cli.seed.unwrap())
+        .field("num_nodes", nodes.len())
+        .field("delta", delta)
+        .field("num_colors", report.num_colors)
+        .field("converged", report.result.is_ok())
+        .string("status", status)
+        .field("rounds", stats.rounds)
+        .field("messages", stats.messages)
+        .field("dropped", stats.dropped)
+        .array("newly_fixed", &stats.newly_fixed)
+        .field("valid", conflicts.is_empty())
+        .array("conflicts", conflicts.iter().map(|(u, v)| format!("[{},{}]", u + id_offset, v + id_offset)))
+        .field("colors_used", colors_used(nodes))
+        .array("histogram", histogram)
+        .optional("clique_lower_bound", report.lower_bound)
+        .optional("chromatic_number", report.chromatic_number)
+        .optional("trials", trials)
+        .optional("attempts", report.attempts)
+        .optional("colors_before_reduce", report.colors_before_reduce)
+        .array("coloring", coloring);
+    writeln!(out, "{json}").unwrap();
+}
+
+/// returns the string as a JSON string literal including the quotes, escaping quotes, backslashes and control characters
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
         }
     }
+    escaped.push('"');
+    escaped
+}
 
-    (g.into_graph(), nodes, (num_nodes - 1).min(4))
+/// a JSON object which is built one field at a time and written with `Display`
+/// the fields keep the order in which they were added
+struct JsonObject {
+    fields: Vec<String>,
 }
 
+impl JsonObject {
+    fn new() -> Self {
+        JsonObject { fields: Vec::new() }
+    }
 
-fn distributed_randomized_coloring_algorithm(graph: &VecGraph, nodes: &mut Vec<Node>, delta: usize, verbose: bool) {
-    // we have delta + 1 available color
-    let list_of_colors: HashSet<Color> = (0..=delta).collect();
-    assert_eq!(list_of_colors.len(), delta + 1);
+    /// adds a field whose value is already valid JSON, e.g. a number, a boolean or a nested object
+    fn field(mut self, key: &str, value: impl Display) -> Self {
+        self.fields.push(format!("{}:{value}", json_string(key)));
+        self
+    }
 
-    if verbose {
-        println!("Starting algorithm with delta = {delta}");
+    /// adds a string field, the value is escaped
+    fn string(self, key: &str, value: &str) -> Self {
+        self.field(key, json_string(value))
     }
-    let mut round = 1;
-    let mut rng = thread_rng();
 
-    // in the first round no node has a permanent color, so everybody chooses a random color
-    for node in nodes.iter_mut() {
-        let random_color = list_of_colors.iter().choose(&mut rng).unwrap();
-        node.coloring = Candidate(*random_color);
-        if verbose {
-            println!("node {:3} chose color {:?}", node.id, node.coloring);
+    /// adds a field which is `null` if there is no value
+    fn optional(self, key: &str, value: Option<impl Display>) -> Self {
+        match value {
+            Some(value) => self.field(key, value),
+            None => self.field(key, "null"),
         }
     }
 
-    loop {
-        if verbose {
-            println!("\nStarting round {round}");
-        }
+    /// adds an array of values which are already valid JSON
+    fn array(self, key: &str, items: impl IntoIterator<Item = impl Display>) -> Self {
+        let items: Vec<String> = items.into_iter().map(|item| item.to_string()).collect();
+        self.field(key, format_args!("[{}]", items.join(",")))
+    }
+}
 
-        // exchange color with all neighbors
-        for e in graph.edges() {
-            let (u, v) = graph.enodes(e);
-            let c = nodes[u.index()].coloring;
-            nodes[v.index()].inbox.push(c);
+impl Display for JsonObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{}}}", self.fields.join(","))
+    }
+}
+
+/// prints the color of every node ordered by id as CSV into `out`, `id_offset` is added to the printed ids
+/// nothing is printed if the algorithm didn't converge
+fn print_csv(out: &mut dyn Write, nodes: &[Node], report: &Report, id_offset: usize) {
+    if let Err(e) = &report.result {
+        eprintln!("Algorithm did not converge: {e}");
+        return;
+    }
 
-            if verbose {
-                println!("node {:3}: sending to node {:3}:  {:?}", u.index(), v.index(), c);
+    writeln!(out, "id,color").unwrap();
+    for node in nodes {
+        writeln!(out, "{},{}", node.id + id_offset, node.coloring.color()).unwrap();
+    }
+}
+
+/// prints the result of the algorithm in a human readable form
+/// the color of each node is written into `out`, the summary is printed to stdout unless quiet is set
+/// the lower bound is only printed if it was computed
+fn print_human(out: &mut dyn Write, cli: &Cli, graph: &VecGraph, nodes: &[Node], report: &Report) {
+    let id_offset = usize::from(cli.one_indexed);
+    let stats = match &report.result {
+        Ok(stats) => stats,
+        Err(e) if cli.quiet => {
+            eprintln!("Algorithm did not converge: {e}");
+            return;
+        }
+        Err(e) => {
+            println!("Algorithm did not converge: {e}");
+            for node in nodes.iter().filter(|n| matches!(n.coloring, Coloring::Candidate(_))) {
+                println!("node {:3} still has candidate color {:3}", node.id + id_offset, node.coloring.color());
+            }
+            if let Some(trials) = &report.trials {
+                print_trials(trials);
             }
+            return;
         }
+    };
+
+    let candidates = nodes.iter().filter(|n| matches!(n.coloring, Coloring::Candidate(_))).count();
+    if !cli.quiet && cli.algorithm == Algorithm::Distributed && candidates > 0 {
+        println!("All priority nodes are permanent after {} rounds, {candidates} nodes are still candidates", stats.rounds);
+    } else if !cli.quiet && cli.algorithm == Algorithm::Distributed {
+        println!("Converged after {} rounds", stats.rounds);
+        if let Some(expected) = report.expected_rounds {
+            println!("Observed {} rounds vs expected ~{expected:.1}", stats.rounds);
+        }
+        println!("Total messages: {}", stats.messages);
+        if let Some(rounds) = report.rounds_without_drops {
+            println!("Dropped {} messages, without dropping {rounds} rounds would be needed", stats.dropped);
+        }
+        print_convergence(nodes.len(), stats);
+    }
 
-        let has_candidate_color = |n: &&mut Node| match n.coloring {
-            Candidate(_) => true,
-            Permanent(_) => false
+    for node in nodes.iter() {
+        let state = match node.coloring {
+            Coloring::Permanent(_) => "permanent",
+            Coloring::Candidate(_) => "candidate",
         };
+        writeln!(out, "node {:3} has {state} color {:3}", node.id + id_offset, node.coloring.color()).unwrap();
+    }
+    out.flush().unwrap();
 
-        // for all non permanent nodes compute available set of colors and permanently color if possible
-        // if not do next iteration and choose new random color
-        for node in nodes.iter_mut().filter(has_candidate_color) {
-            if verbose {
-                println!("node {:3} is none permanent", node.id);
-            }
-            let mut available_colors = list_of_colors.clone();
-            let mut candidate_colors = list_of_colors.clone();
-
-            for coloring in &node.inbox {
-                if let Permanent(v) = coloring {
-                    available_colors.remove(v);
-                }
-                candidate_colors.remove(coloring.color());
-            }
+    if cli.quiet {
+        return;
+    }
 
-            if verbose {
-                println!("node {:3}: non permanent colors {:?}", node.id, available_colors);
-                println!("node {:3}: colors not used by neighbors {:?}", node.id, candidate_colors);
-            }
+    print_validation(graph, nodes, id_offset);
+    match report.lower_bound {
+        Some(c) => println!("Used {} of {} colors (lower bound >= {c})", colors_used(nodes), report.num_colors),
+        None => println!("Used {} of {} colors", colors_used(nodes), report.num_colors),
+    }
+    if let Some(before) = report.colors_before_reduce {
+        println!("The reduce pass changed the colors used from {before} to {}", colors_used(nodes));
+    }
+    match report.chromatic_number {
+        Some(k) if colors_used(nodes) == k => println!("The coloring is optimal, the graph needs {k} colors"),
+        Some(k) => println!("The coloring is not optimal, the graph only needs {k} colors"),
+        None => {}
+    }
+    if let Some(attempts) = report.attempts {
+        let seed = cli.seed.unwrap().wrapping_add(attempts - 1);
+        match report.chromatic_number {
+            Some(k) if colors_used(nodes) == k => println!("Found an optimal coloring after {attempts} attempts with seed {seed}"),
+            _ => println!("No optimal coloring found in {attempts} attempts"),
+        }
+    }
+    if cli.histogram {
+        print_histogram(nodes);
+    }
+    if let Some(unbiased_colors) = report.unbiased_colors {
+        println!("Without color bias {unbiased_colors} colors would be used");
+    }
+    if let Some(trials) = &report.trials {
+        print_trials(trials);
+    }
+}
 
-            // reset inbox
-            node.inbox.clear();
+/// prints how many nodes have each color, the most used color first
+fn print_histogram(nodes: &[Node]) {
+    let histogram = color_histogram(nodes);
+    let largest = histogram.first().map_or(0, |&(_, count)| count);
 
-            // check if node can go permanent
-            if candidate_colors.contains(node.coloring.color()) {
-                if verbose {
-                    println!("node {:3}: my color {:?} is used by nobody lets go permanent", node.id, node.coloring);
-                }
-                node.coloring = Permanent(*node.coloring.color());
-                continue;
-            }
+    println!("\nColor usage:");
+    for (color, count) in histogram {
+        // the bars are scaled so the longest one has 50 characters
+        let bar = "#".repeat((count * 50).div_ceil(largest));
+        println!("color {color:3}: {count:6} {bar}");
+    }
+}
 
-            let random_color = available_colors.iter().choose(&mut rng).unwrap();
-            node.coloring = Candidate(*random_color);
+/// prints the statistics over all trials
+fn print_trials(trials: &TrialSummary) {
+    println!("\nOver {} trials, {} did not converge", trials.trials, trials.failed);
+    let print_summary = |name: &str, summary: &Option<Summary>| match summary {
+        Some(s) => println!("{name}: min {}, max {}, mean {:.2}, stddev {:.2}", s.min, s.max, s.mean, s.stddev),
+        None => println!("{name}: no trial converged"),
+    };
+    print_summary("Rounds", &trials.rounds);
+    print_summary("Colors used", &trials.colors_used);
+}
 
-            if verbose {
-                println!("node {:3} cannot be fixed chose new color {:?}", node.id, node.coloring);
+/// creates the logger printing to stderr with verbose, `None` otherwise
+fn logger(cli: &Cli) -> Option<Logger> {
+    let level = match cli.log_level {
+        LogLevelArg::Info => LogLevel::Info,
+        LogLevelArg::Debug => LogLevel::Debug,
+        LogLevelArg::Trace => LogLevel::Trace,
+    };
+    cli.verbose.then(|| Logger::stderr(level))
+}
+
+/// creates the options for the distributed algorithm from the command line arguments
+fn algorithm_options(cli: &Cli) -> AlgorithmOptions {
+    AlgorithmOptions {
+        max_rounds: cli.max_rounds as usize,
+        schedule: match cli.schedule {
+            ScheduleArg::Sync => Schedule::Synchronous,
+            ScheduleArg::Async => Schedule::Asynchronous,
+        },
+        initial_coloring: match cli.init {
+            InitArg::Random => InitialColoring::Random,
+            InitArg::Deterministic => InitialColoring::Deterministic,
+        },
+        color_bias: cli.color_bias,
+        logger: logger(cli),
+        progress: cli.progress,
+        step: cli.step,
+        timeout: cli.timeout_ms.map(Duration::from_millis),
+        priority_nodes: cli.priority_nodes.clone(),
+        precolored: cli.precolor.clone(),
+        drop_prob: cli.drop_prob,
+    }
+}
+
+/// returns the number of colors the distributed algorithm uses, delta + 1 unless overridden
+fn num_colors(cli: &Cli, delta: usize) -> usize {
+    cli.colors.map_or(delta + 1, |k| k as usize)
+}
+
+/// returns the degrees of all vertices sorted from largest to smallest
+fn degree_sequence(graph: &VecGraph) -> Vec<usize> {
+    let mut degrees = degrees(graph);
+    degrees.sort_unstable_by(|a, b| b.cmp(a));
+    degrees
+}
+
+/// prints the size and the degrees of the graph without coloring it
+/// with `with_sequence` the degree sequence is printed too
+fn describe_graph(graph: &VecGraph, delta: usize, format: Format, with_sequence: bool) {
+    let degrees = degrees(graph);
+    let num_edges = degrees.iter().sum::<usize>() / 2;
+    let min_degree = degrees.iter().min().copied().unwrap_or(0);
+    let avg_degree = if degrees.is_empty() { 0.0 } else { 2.0 * num_edges as f64 / degrees.len() as f64 };
+    let sequence: Vec<String> = degree_sequence(graph).iter().map(|d| d.to_string()).collect();
+
+    match format {
+        Format::Human => {
+            println!("Vertices: {}", degrees.len());
+            println!("Edges: {num_edges}");
+            println!("Max degree (delta): {delta}");
+            println!("Min degree: {min_degree}");
+            println!("Average degree: {avg_degree:.2}");
+            if with_sequence {
+                println!("Degree sequence: {}", sequence.join(" "));
             }
         }
+        Format::Json => {
+            let sequence = if with_sequence { format!(",\"degree_sequence\":[{}]", sequence.join(",")) } else { String::new() };
+            println!("{{\"num_nodes\":{},\"num_edges\":{num_edges},\"delta\":{delta},\"min_degree\":{min_degree},\"avg_degree\":{avg_degree}{sequence}}}",
+                     degrees.len());
+        }
+        Format::Csv if with_sequence => {
+            println!("num_nodes,num_edges,delta,min_degree,avg_degree,degree_sequence");
+            println!("{},{num_edges},{delta},{min_degree},{avg_degree},{}", degrees.len(), sequence.join(" "));
+        }
+        Format::Csv => {
+            println!("num_nodes,num_edges,delta,min_degree,avg_degree");
+            println!("{},{num_edges},{delta},{min_degree},{avg_degree}", degrees.len());
+        }
+    }
+}
 
-        // check if the graph has a valid coloring
-        if nodes.iter_mut().filter(has_candidate_color).next().is_none() {
-            if verbose {
-                println!("no candidate colors left, coloring should be fixed");
-                println!("Finished after {round} rounds\n");
-            }
-            break;
+/// checks that the precolored nodes exist, have one of the available colors
+/// and that no two adjacent precolored nodes have the same color
+fn check_precoloring(graph: &VecGraph, num_nodes: usize, precolor: &[(usize, usize)], num_colors: usize) -> Result<(), String> {
+    let mut colors = vec![None; num_nodes];
+
+    for &(v, color) in precolor {
+        if v >= num_nodes {
+            return Err(format!("The precolored node {v} doesn't exist, the graph only has {num_nodes} nodes"));
+        }
+        if color >= num_colors {
+            return Err(format!("The precolored node {v} has color {color} but only the colors 0 to {} are available", num_colors - 1));
+        }
+        if colors[v].is_some_and(|c| c != color) {
+            return Err(format!("The node {v} is precolored with two different colors"));
         }
+        colors[v] = Some(color);
+    }
 
-        // print new coloring
-        for node in nodes.iter_mut() {
-            if verbose {
-                println!("node {:3} has color {:?}", node.id, node.coloring);
+    for e in graph.edges() {
+        let (u, v) = graph.enodes(e);
+        if let (Some(a), Some(b)) = (colors[u.index()], colors[v.index()]) {
+            if a == b {
+                return Err(format!("The adjacent nodes {} and {} are both precolored with color {a}", u.index(), v.index()));
             }
         }
+    }
+
+    Ok(())
+}
 
-        round += 1;
+/// colors the graph twice starting from the same rng state and prints whether the colorings and rounds are the same
+/// a difference means something else than the seed influences the result, then the program exits with an error
+fn check_determinism(graph: &VecGraph, nodes: &[Node], delta: usize, cli: &Cli, rng: &StdRng) {
+    let run = || {
+        let mut nodes = nodes.to_vec();
+        let result = color_nodes(graph, &mut nodes, delta, cli, &mut rng.clone());
+        let coloring: Vec<Coloring> = nodes.iter().map(|n| n.coloring).collect();
+        (result.map(|stats| stats.rounds).map_err(|e| e.to_string()), coloring)
+    };
+    let (first_result, first) = run();
+    let (second_result, second) = run();
+
+    // Coloring can't be compared directly, so the state and color of every node are compared
+    let key = |c: &Coloring| (matches!(c, Coloring::Permanent(_)), *c.color());
+    let differing = first.iter().zip(&second).position(|(a, b)| key(a) != key(b));
+    match differing {
+        None if first_result == second_result => println!("PASS: both runs with seed {} gave the same coloring", cli.seed.unwrap()),
+        None => {
+            println!("FAIL: the colorings are the same but the runs ended differently: {first_result:?} and {second_result:?}");
+            std::process::exit(EXIT_NOT_DETERMINISTIC);
+        }
+        Some(v) => {
+            println!("FAIL: node {} has {:?} in the first run and {:?} in the second run", v + usize::from(cli.one_indexed), first[v], second[v]);
+            std::process::exit(EXIT_NOT_DETERMINISTIC);
+        }
     }
 }
 
+/// assigns the coloring read from the file given with --coloring to the nodes and prints whether it's valid
+/// exits with an error if the file can't be read or the coloring isn't valid
+fn validate_coloring(graph: &VecGraph, nodes: &mut [Node], cli: &Cli) {
+    let path = cli.coloring.as_ref().unwrap();
+    let colors = load_coloring(path, nodes.len(), usize::from(cli.one_indexed)).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+
+    for (node, color) in nodes.iter_mut().zip(colors) {
+        node.coloring = Coloring::Permanent(color);
+    }
 
-/// this is the test case, it generates a complete graph with 200 vertices
-/// in such a case each color may only be used once
-/// we check this by checking the length of the deduplicated vector containing
-/// all colors has the same length as the vector containing all the nodes
-fn test_case(verbose: bool) {
-    let (graph, mut nodes, delta) = complete_graph(200);
-    distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta, verbose);
+    print_validation(graph, nodes, usize::from(cli.one_indexed));
+    println!("Used {} colors", colors_used(nodes));
+    if !find_conflicts(graph, nodes).is_empty() {
+        std::process::exit(EXIT_INVALID_COLORING);
+    }
+}
 
-    println!("\n\nAlgorithm finished:");
-    for node in nodes.iter_mut() {
-        println!("node {:3} has permanent color {:3}", node.id, node.coloring.color());
+/// checks the priority and precolored nodes given on the command line against the graph which is about to be colored
+/// with `num_colors` colors, prints the problem and exits if they don't fit
+fn check_node_options(graph: &VecGraph, num_colors: usize, cli: &Cli) {
+    if let Some(v) = cli.priority_nodes.iter().find(|&&v| v >= graph.num_nodes()) {
+        eprintln!("The priority node {v} doesn't exist, the graph only has {} nodes", graph.num_nodes());
+        std::process::exit(1);
+    }
+    if let Err(e) = check_precoloring(graph, graph.num_nodes(), &cli.precolor, num_colors) {
+        eprintln!("{e}");
+        std::process::exit(1);
     }
+}
 
-    // in a complete graph, each color must only be used once
-    nodes.sort_by(|a, b| a.coloring.color().cmp(&b.coloring.color()));
-    println!("\nSorting by color:");
-    for node in nodes.iter_mut() {
-        println!("node {:3} has permanent color {:3}", node.id, node.coloring.color());
+/// colors the graphs of all run modes which only depend on the number of nodes and prints a table
+/// comparing them, every graph is colored with an rng seeded with `seed`
+fn compare_modes(cli: &Cli, num_nodes: usize, seed: u64) {
+    let modes = [RunMode::CompleteGraph, RunMode::Chain, RunMode::Hydrocarbon, RunMode::Cycle, RunMode::Wheel, RunMode::Tree, RunMode::Random];
+    let generators = generators();
+
+    println!("{:<16} {:>6} {:>6} {:>7} {:>7} {:>6}", "mode", "nodes", "delta", "rounds", "colors", "valid");
+    for mode in modes {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (graph, mut nodes, delta) = (generators[&mode].build)(cli, num_nodes, &mut rng);
+        check_node_options(&graph, num_colors(cli, delta), cli);
+
+        let result = color_nodes(&graph, &mut nodes, delta, cli, &mut rng);
+        let rounds = match &result {
+            Ok(stats) => stats.rounds.to_string(),
+            Err(_) => "-".to_string(),
+        };
+        let valid = if result.is_ok() && find_conflicts(&graph, &nodes).is_empty() { "yes" } else { "no" };
+        let name = mode.to_possible_value().unwrap();
+        println!("{:<16} {:>6} {:>6} {:>7} {:>7} {:>6}", name.get_name(), nodes.len(), delta, rounds, colors_used(&nodes), valid);
     }
+}
 
-    // the length must be the same after the deduplication
-    let all_nodes_len = nodes.len();
-    nodes.dedup_by_key(|n| *n.coloring.color());
-    assert_eq!(nodes.len(), all_nodes_len);
+/// returns whether the size of the graph of the run mode is given by the number of nodes
+fn sized_by_num(mode: RunMode) -> bool {
+    generators().get(&mode).is_some_and(|family| matches!(family.size, GraphSize::Num))
 }
 
-#[derive(Parser)]
-#[command(author, version, about, long_about = None)]
-struct Cli {
-    /// Run mode
-    #[arg(short, long, value_enum, default_value_t = RunMode::Testcase)]
-    mode: RunMode,
+/// colors the graph of the run mode with `num_nodes` nodes using the seed and asserts that the coloring is valid
+/// unlike the test case for the complete graph neighbors only need different colors, so any graph can be checked
+fn run_test(cli: &Cli, mode: RunMode, num_nodes: usize, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (graph, mut nodes, delta) = (generators()[&mode].build)(cli, num_nodes, &mut rng);
+    check_node_options(&graph, num_colors(cli, delta), cli);
+
+    let stats = color_nodes(&graph, &mut nodes, delta, cli, &mut rng)
+        .unwrap_or_else(|e| panic!("{mode:?} with {num_nodes} nodes and seed {seed} did not converge: {e}"));
+    if let Some(&(u, v)) = find_conflicts(&graph, &nodes).first() {
+        panic!("{mode:?} with {num_nodes} nodes and seed {seed}: node {u} and node {v} are adjacent and both have color {}",
+               nodes[u].coloring.color());
+    }
 
-    /// Number of nodes to be used, has no effect for testcase run mode
-    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser ! (u64).range(1..))]
-    num: u64,
+    println!("{mode:?}: valid coloring with {} colors after {} rounds", colors_used(&nodes), stats.rounds);
+}
 
-    /// Print additional information while running the algorithm
-    #[arg(short, long)]
-    verbose: bool,
+/// colors the graph of the run mode with each of the given numbers of nodes, every size uses the same seed
+/// prints the rounds, colors used and time needed for the coloring in milliseconds as CSV
+/// only run modes whose size is given by the number of nodes can be swept
+fn scaling_sweep(cli: &Cli, sizes: &[u64], seed: u64) {
+    if !sized_by_num(cli.mode) {
+        eprintln!("--scaling-sweep needs a run mode whose size is given by the number of nodes, not {:?}", cli.mode);
+        std::process::exit(1);
+    }
 
-    /// Create a dot file of the graph to visualize with graphviz, has no effect for testcase run mode
-    #[arg(short, long)]
-    dotfile: Option<String>,
+    let generator = generators()[&cli.mode].build;
+    println!("size,rounds,colors,ms");
+    for &size in sizes {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (graph, mut nodes, delta) = generator(cli, size as usize, &mut rng);
+        check_node_options(&graph, num_colors(cli, delta), cli);
+
+        let start = Instant::now();
+        let result = color_nodes(&graph, &mut nodes, delta, cli, &mut rng);
+        let ms = start.elapsed().as_millis();
+
+        // a run that didn't converge has no meaningful number of rounds or colors
+        match result {
+            Ok(stats) => println!("{size},{},{},{ms}", stats.rounds, colors_used(&nodes)),
+            Err(_) => println!("{size},,,{ms}"),
+        }
+    }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
-enum RunMode {
-    Testcase,
-    CompleteGraph,
-    Chain,
-    Hydrocarbon,
+/// builds the graph of a run mode from the command line options, the number of nodes and the rng
+/// returns the graph, a vector of nodes and delta (max degree)
+type Generator = fn(&Cli, usize, &mut StdRng) -> (VecGraph, Vec<Node>, usize);
+
+/// how the number of nodes of a graph family is given
+#[derive(Copy, Clone)]
+enum GraphSize {
+    /// by `--num`
+    Num,
+    /// by other command line options, e.g. the dimensions of a grid
+    Options(fn(&Cli) -> usize),
+    /// by the input file, it's only known after loading the graph
+    Loaded,
+}
+
+/// everything needed to run the algorithm on the graphs of a run mode
+#[derive(Copy, Clone)]
+struct GraphFamily {
+    build: Generator,
+    size: GraphSize,
+    /// returns the chromatic number of the graph with the given number of nodes, `None` for random and loaded graphs
+    /// and if it depends on more than the number of nodes, e.g. on the dimensions of a torus
+    chromatic_number: fn(usize) -> Option<usize>,
 }
 
-fn graph_to_dot(file_path: String, graph: VecGraph, nodes: &Vec<Node>, delta: usize, verbose: bool) {
-    if verbose {
-        println!("Writing dot file into '{}'", file_path);
+impl GraphFamily {
+    fn new(build: Generator, size: GraphSize) -> Self {
+        GraphFamily { build, size, chromatic_number: |_| None }
     }
 
-    let file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(file_path);
+    fn with_chromatic_number(self, chromatic_number: fn(usize) -> Option<usize>) -> Self {
+        GraphFamily { chromatic_number, ..self }
+    }
+}
 
-    if file.is_err() {
-        panic!("Writing dot file failed: {:?}", file.err().unwrap());
+/// the chromatic number of a bipartite graph, a graph with a single vertex doesn't have any edges and needs just one color
+fn bipartite(num_nodes: usize) -> Option<usize> {
+    Some(if num_nodes == 1 { 1 } else { 2 })
+}
+
+/// returns the graph family of every run mode except testcase and verify, a new run mode only has to be added here
+fn generators() -> BTreeMap<RunMode, GraphFamily> {
+    use GraphSize::{Loaded, Num, Options};
+    let mut generators = BTreeMap::new();
+
+    generators.insert(RunMode::CompleteGraph, GraphFamily::new(|_, n, _| complete_graph(n), Num).with_chromatic_number(Some));
+    generators.insert(RunMode::Chain, GraphFamily::new(|_, n, _| chain(n), Num).with_chromatic_number(bipartite));
+    generators.insert(RunMode::Hydrocarbon, GraphFamily::new(|_, n, _| hydrocarbon(n), Num).with_chromatic_number(bipartite));
+    generators.insert(RunMode::Cycle, GraphFamily::new(|_, n, _| cycle(n), Num)
+        .with_chromatic_number(|n| if n >= 3 { Some(2 + n % 2) } else { bipartite(n) }));
+    let grid_size = Options(|cli| {
+        let (rows, cols) = cli.grid.unwrap();
+        rows * cols
+    });
+    generators.insert(RunMode::Grid, GraphFamily::new(|cli, _, _| {
+        let (rows, cols) = cli.grid.unwrap();
+        grid(rows, cols)
+    }, grid_size).with_chromatic_number(bipartite));
+    generators.insert(RunMode::KingGrid, GraphFamily::new(|cli, _, _| {
+        let (rows, cols) = cli.grid.unwrap();
+        king_grid(rows, cols)
+    }, grid_size));
+    generators.insert(RunMode::Torus, GraphFamily::new(|cli, _, _| {
+        let (rows, cols) = cli.grid.unwrap();
+        torus(rows, cols)
+    }, grid_size));
+    generators.insert(RunMode::Random, GraphFamily::new(|cli, n, rng| random_graph(n, cli.probability, rng), Num));
+    generators.insert(RunMode::RandomRegular, GraphFamily::new(|cli, n, rng| {
+        let degree = cli.degree.unwrap() as usize;
+        if n * degree % 2 == 1 || degree >= n {
+            eprintln!("A {degree}-regular graph with {n} vertices doesn't exist, the degree must be smaller than the number of vertices and their product must be even");
+            std::process::exit(1);
+        }
+        random_regular(n, degree, rng)
+    }, Num));
+    generators.insert(RunMode::Clustered, GraphFamily::new(|cli, _, rng| {
+        clustered(cli.clusters.unwrap() as usize, cli.cluster_size.unwrap() as usize, cli.inter_edges as usize, rng)
+    }, Options(|cli| (cli.clusters.unwrap() * cli.cluster_size.unwrap()) as usize)));
+    // the rim needs 2 or 3 colors and the hub adjacent to all of them one more, with 3 nodes the wheel is a triangle
+    generators.insert(RunMode::Wheel, GraphFamily::new(|_, n, _| wheel(n), Num).with_chromatic_number(|n| match n {
+        4.. => Some(3 + (n - 1) % 2),
+        3 => Some(3),
+        _ => Some(n),
+    }));
+    generators.insert(RunMode::Windmill, GraphFamily::new(|cli, _, _| windmill(cli.triangles.unwrap() as usize),
+        Options(|cli| 2 * cli.triangles.unwrap() as usize + 1)).with_chromatic_number(|_| Some(3)));
+    generators.insert(RunMode::Barbell, GraphFamily::new(|cli, _, _| barbell(cli.clique_size.unwrap() as usize, cli.path_len as usize),
+        Options(|cli| (2 * cli.clique_size.unwrap() + cli.path_len) as usize)));
+    generators.insert(RunMode::Tree, GraphFamily::new(|cli, n, _| kary_tree(n, cli.branching as usize), Num).with_chromatic_number(bipartite));
+    generators.insert(RunMode::GeneralizedPetersen, GraphFamily::new(|cli, _, _| {
+        let (n, k) = (cli.outer.unwrap() as usize, cli.inner_step.unwrap() as usize);
+        if 2 * k >= n {
+            eprintln!("The step {k} must be smaller than half of the {n} outer vertices");
+            std::process::exit(1);
+        }
+        generalized_petersen(n, k)
+    }, Options(|cli| 2 * cli.outer.unwrap() as usize)));
+    generators.insert(RunMode::Petersen, GraphFamily::new(|_, _, _| petersen(), Options(|_| 10)).with_chromatic_number(|_| Some(3)));
+    generators.insert(RunMode::Hypercube, GraphFamily::new(|cli, _, _| hypercube(cli.dimension.unwrap() as usize),
+        Options(|cli| 1 << cli.dimension.unwrap())).with_chromatic_number(bipartite));
+    // M_k has 3 * 2^(k-2) - 1 vertices for k >= 2 and M_1 a single vertex
+    generators.insert(RunMode::Mycielski, GraphFamily::new(|cli, _, _| mycielski(cli.chromatic_number.unwrap() as usize),
+        Options(|cli| match cli.chromatic_number.unwrap() {
+            1 => 1,
+            k => 3 * (1 << (k - 2)) - 1,
+        })).with_chromatic_number(|n| if n == 1 { Some(1) } else { Some(((n + 1) / 3).trailing_zeros() as usize + 2) }));
+    generators.insert(RunMode::Caterpillar, GraphFamily::new(|cli, _, _| caterpillar(cli.spine.unwrap() as usize, cli.leaves as usize),
+        Options(|cli| (cli.spine.unwrap() * (cli.leaves + 1)) as usize)).with_chromatic_number(bipartite));
+    generators.insert(RunMode::Edges, GraphFamily::new(|cli, _, _| graph_from_edge_pairs(&cli.edges),
+        Options(|cli| cli.edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0))));
+    generators.insert(RunMode::File, GraphFamily::new(|cli, _, _| loaded_graph(cli, load_edge_list(cli.input.as_ref().unwrap())), Loaded));
+    generators.insert(RunMode::Dimacs, GraphFamily::new(|cli, _, _| loaded_graph(cli, load_dimacs(cli.input.as_ref().unwrap())), Loaded));
+    generators.insert(RunMode::Dot, GraphFamily::new(|cli, _, _| loaded_graph(cli, load_dot(cli.input.as_ref().unwrap())), Loaded));
+    generators.insert(RunMode::Matrix, GraphFamily::new(|cli, _, _| loaded_graph(cli, load_adjacency_matrix(cli.input.as_ref().unwrap())), Loaded));
+
+    generators
+}
+
+/// returns the graph loaded from the input file, exits with an error if it couldn't be read
+fn loaded_graph(cli: &Cli, loaded: Result<(VecGraph, Vec<Node>, usize), String>) -> (VecGraph, Vec<Node>, usize) {
+    let (graph, nodes, delta) = loaded.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    if cli.format == Format::Human && !cli.quiet {
+        println!("Loaded graph with {} vertices", nodes.len());
     }
+    (graph, nodes, delta)
+}
 
-    let mut file = file.unwrap();
-    file.write("strict graph {\n".as_bytes()).unwrap();
+/// colors the nodes using the algorithm chosen on the command line
+fn color_nodes(graph: &VecGraph, nodes: &mut [Node], delta: usize, cli: &Cli, rng: &mut StdRng) -> Result<AlgorithmStats, ColoringError> {
+    match cli.algorithm {
+        Algorithm::Distributed => distributed_randomized_coloring_algorithm(graph, nodes, num_colors(cli, delta), &algorithm_options(cli), rng),
+        Algorithm::Greedy => {
+            // the sequential algorithm doesn't need any rounds or messages
+            greedy_coloring(graph, nodes, delta, &cli.precolor);
+            Ok(AlgorithmStats::default())
+        }
+        Algorithm::Dsatur => {
+            dsatur_coloring(graph, nodes, delta, &cli.precolor);
+            Ok(AlgorithmStats::default())
+        }
+    }
+}
 
-    for e in graph.edges() {
-        let (u, v) = graph.enodes(e);
-        file.write(format!("n{} -- n{}\n", u.index(), v.index()).as_bytes()).unwrap();
+/// runs the algorithm on the generated graph, prints the resulting coloring and validates it
+/// with multiple trials trial `i` uses the seed plus `i` and only the last trial is printed in detail
+fn run_algorithm(mut graph: VecGraph, mut nodes: Vec<Node>, mut delta: usize, cli: &Cli, rng: &mut StdRng) {
+    if cli.complement {
+        (graph, nodes, delta) = complement(&graph);
+    }
+
+    if let Some(path) = &cli.export_edges {
+        log_message!(logger(cli).as_ref(), LogLevel::Info, "Writing edge list into '{}'", path);
+        fs::write(path, format_edge_list(&graph)).unwrap_or_else(|e| panic!("Writing edge list failed: {e:?}"));
     }
 
-    let unique_colors: Vec<String> = (0..=delta).into_iter().map(|_| {
-        let mut rng = thread_rng();
-        let dist = Uniform::new(0, 200);
-        format!("#{:02x}{:02x}{:02x}", rng.sample(dist), rng.sample(dist), rng.sample(dist))
-    }).collect();
+    check_node_options(&graph, num_colors(cli, delta), cli);
 
-    if verbose {
-        println!("colors generated = {:#?}", unique_colors);
+    if cli.validate_only {
+        validate_coloring(&graph, &mut nodes, cli);
+        return;
     }
 
-    for (id, node) in nodes.iter().enumerate() {
-        let color = &unique_colors[*node.coloring.color()];
-        file.write(format!("n{} [color=\"black\", fillcolor=\"{}\", style=filled]\n", id, color).as_bytes()).unwrap();
+    if cli.check_determinism {
+        check_determinism(&graph, &nodes, delta, cli, rng);
+        return;
     }
 
-    file.write("}\n".as_bytes()).unwrap();
-    file.flush().unwrap();
-}
+    if cli.dry_run {
+        describe_graph(&graph, delta, cli.format, cli.degree_sequence);
+        return;
+    }
 
-fn main() {
-    let cli = Cli::parse();
-    let num_nodes = cli.num as usize;
-    println!("Running in {:?} mode with {num_nodes} vertices", cli.mode);
+    // only the human output has room for the degree sequence, otherwise it would break the JSON or CSV
+    if cli.degree_sequence {
+        let sequence: Vec<String> = degree_sequence(&graph).iter().map(|d| d.to_string()).collect();
+        match cli.format {
+            Format::Human if !cli.quiet => println!("Degree sequence: {}", sequence.join(" ")),
+            _ => eprintln!("Degree sequence: {}", sequence.join(" ")),
+        }
+    }
 
-    match cli.mode {
-        RunMode::Testcase => {
-            test_case(cli.verbose);
+    let mut rounds = Vec::new();
+    let mut colors = Vec::new();
+    let mut failed = 0;
+    let mut record_trial = |result: &Result<AlgorithmStats, ColoringError>, nodes: &[Node]| match result {
+        Ok(stats) => {
+            rounds.push(stats.rounds);
+            colors.push(colors_used(nodes));
+        }
+        Err(_) => failed += 1,
+    };
+
+    if cli.trials > 1 {
+        let seed = cli.seed.unwrap();
+        for i in 0..cli.trials - 1 {
+            let mut trial_nodes = nodes.clone();
+            let result = color_nodes(&graph, &mut trial_nodes, delta, cli, &mut StdRng::seed_from_u64(seed.wrapping_add(i)));
+            record_trial(&result, &trial_nodes);
         }
-        RunMode::CompleteGraph => {
-            let (graph, mut nodes, delta) = complete_graph(num_nodes);
-            distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta, cli.verbose);
+        *rng = StdRng::seed_from_u64(seed.wrapping_add(cli.trials - 1));
+    }
 
-            for node in nodes.iter_mut() {
-                println!("node {:3} has permanent color {:3}", node.id, node.coloring.color());
-            }
+    // attempt `i` uses the seed plus `i` like the trials, the rng is reseeded for the last attempt so it's printed
+    // the chromatic number is only known for the generated graph itself and not for its complement
+    let chromatic_number = if cli.complement { None } else { (generators()[&cli.mode].chromatic_number)(nodes.len()) };
+    let attempts = if cli.repeat_until_optimal {
+        let Some(k) = chromatic_number else {
+            eprintln!("--repeat-until-optimal needs a run mode with a known chromatic number");
+            std::process::exit(1);
+        };
 
-            if cli.dotfile.is_some() {
-                graph_to_dot(cli.dotfile.unwrap(), graph, &nodes, delta, cli.verbose);
+        let seed = cli.seed.unwrap();
+        let mut attempts = 0;
+        while attempts < cli.max_attempts {
+            attempts += 1;
+            let mut attempt_nodes = nodes.clone();
+            let result = color_nodes(&graph, &mut attempt_nodes, delta, cli, &mut StdRng::seed_from_u64(seed.wrapping_add(attempts - 1)));
+            if result.is_ok() && colors_used(&attempt_nodes) == k {
+                break;
             }
         }
-        RunMode::Chain => {
-            let (graph, mut nodes, delta) = chain(num_nodes);
-            distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta, cli.verbose);
-
-            for node in nodes.iter_mut() {
-                println!("node {:3} has permanent color {:3}", node.id, node.coloring.color());
+        *rng = StdRng::seed_from_u64(seed.wrapping_add(attempts - 1));
+        Some(attempts)
+    } else {
+        None
+    };
+
+    // with a color bias the same run is repeated without it to compare the number of colors used
+    // it uses a copy of the rng so the biased run is the same as without the comparison
+    let unbiased_colors = if cli.algorithm == Algorithm::Distributed && cli.color_bias > 0.0 {
+        let mut unbiased_nodes = nodes.clone();
+        let options = AlgorithmOptions {
+            color_bias: 0.0,
+            logger: None,
+            step: false,
+            ..algorithm_options(cli)
+        };
+        distributed_randomized_coloring_algorithm(&graph, &mut unbiased_nodes, num_colors(cli, delta), &options, &mut rng.clone())
+            .ok()
+            .map(|_| colors_used(&unbiased_nodes))
+    } else {
+        None
+    };
+
+    // with lost messages the same run is repeated without them to compare the number of rounds
+    let rounds_without_drops = if cli.algorithm == Algorithm::Distributed && cli.drop_prob > 0.0 {
+        let options = AlgorithmOptions {
+            drop_prob: 0.0,
+            logger: None,
+            step: false,
+            ..algorithm_options(cli)
+        };
+        distributed_randomized_coloring_algorithm(&graph, &mut nodes.clone(), num_colors(cli, delta), &options, &mut rng.clone())
+            .ok()
+            .map(|stats| stats.rounds)
+    } else {
+        None
+    };
+
+    // only the coloring itself is timed, not creating the graph or printing the result
+    let start = Instant::now();
+    let result = color_nodes(&graph, &mut nodes, delta, cli, rng);
+    log_message!(logger(cli).as_ref(), LogLevel::Info, "Colored in {} ms", start.elapsed().as_millis());
+    record_trial(&result, &nodes);
+
+    // the pass needs a valid coloring, so it's skipped if some nodes still have a candidate color
+    let colors_before_reduce = if cli.reduce && result.is_ok() && nodes.iter().all(|n| matches!(n.coloring, Coloring::Permanent(_))) {
+        let before = colors_used(&nodes);
+        recolor_reduce(&graph, &mut nodes);
+        Some(before)
+    } else {
+        None
+    };
+
+    // finding a maximum clique is exponential, so it is only done for small graphs
+    let lower_bound = if nodes.len() as u64 <= cli.clique_limit {
+        Some(clique_lower_bound(&graph))
+    } else {
+        None
+    };
+
+    // the result goes to stdout unless an output file is given
+    let mut out: Box<dyn Write> = match &cli.output {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("Creating output file '{path}' failed: {e}");
+                std::process::exit(1);
             }
+        },
+        None => Box::new(io::stdout()),
+    };
+
+    let trials = if cli.trials > 1 {
+        Some(TrialSummary {
+            trials: cli.trials,
+            failed,
+            rounds: Summary::of(&rounds),
+            colors_used: Summary::of(&colors),
+        })
+    } else {
+        None
+    };
+    let num_colors = match cli.algorithm {
+        Algorithm::Distributed => num_colors(cli, delta),
+        // the sequential algorithms use at most delta + 1 colors, but a precolored node may have a larger one
+        Algorithm::Greedy | Algorithm::Dsatur => nodes.iter().map(|n| n.coloring.color() + 1).fold(delta + 1, usize::max),
+    };
+    let report = Report {
+        chromatic_number,
+        num_colors,
+        // the analysis assumes every node can always choose from delta + 1 colors
+        expected_rounds: (num_colors == delta + 1).then(|| expected_rounds(nodes.len(), delta)),
+        result,
+        lower_bound,
+        unbiased_colors,
+        trials,
+        attempts,
+        colors_before_reduce,
+        rounds_without_drops,
+    };
+
+    match cli.format {
+        Format::Human => print_human(&mut out, cli, &graph, &nodes, &report),
+        Format::Json => print_json(&mut out, cli, &graph, &nodes, delta, &report),
+        Format::Csv => print_csv(&mut out, &nodes, &report, usize::from(cli.one_indexed)),
+    }
+    out.flush().unwrap();
+
+    if let Some(path) = &cli.dump_state {
+        dump_state(path, &nodes, logger(cli).as_ref());
+    }
+
+    // the warning comes after the result so it isn't lost at the start of a long output
+    let exceeded = cli.warn_if_colors_exceed.filter(|&k| report.result.is_ok() && colors_used(&nodes) as u64 > k);
+    if let Some(k) = exceeded {
+        eprintln!("WARNING: the coloring uses {} colors which is more than the expected {k}", colors_used(&nodes));
+    }
+
+    if report.result.is_ok() {
+        write_graph_files(&graph, &nodes, report.num_colors, cli);
+    }
+
+    // with priority nodes the other nodes may still have conflicting candidate colors, which is expected
+    let complete = nodes.iter().all(|n| matches!(n.coloring, Coloring::Permanent(_)));
+    let code = match &report.result {
+        Err(ColoringError::RoundLimit(_)) => EXIT_ROUND_LIMIT,
+        Err(ColoringError::Timeout(_)) => EXIT_TIMEOUT,
+        Err(ColoringError::InvalidOptions(_)) => 1,
+        Ok(_) if complete && !find_conflicts(&graph, &nodes).is_empty() => EXIT_INVALID_COLORING,
+        Ok(_) if exceeded.is_some() => EXIT_TOO_MANY_COLORS,
+        Ok(_) => 0,
+    };
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// writes the dot, GraphML and SVG files given on the command line, all of them use the same `num_colors` colors
+fn write_graph_files(graph: &VecGraph, nodes: &[Node], num_colors: usize, cli: &Cli) {
+    if cli.dotfile.is_none() && cli.graphml.is_none() && cli.svg.is_none() {
+        return;
+    }
 
-            if cli.dotfile.is_some() {
-                graph_to_dot(cli.dotfile.unwrap(), graph, &nodes, delta, cli.verbose);
+    // the dot, GraphML and SVG file use the same colors
+    let palette = match (&cli.palette, cli.palette_scheme) {
+        (Some(path), _) => {
+            let palette = load_palette(path).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1);
+            });
+            for warning in palette_warnings(&palette, num_colors) {
+                eprintln!("warning: palette '{path}': {warning}");
             }
+            palette
         }
-        RunMode::Hydrocarbon => {
-            let (graph, mut nodes, delta) = hydrocarbon(num_nodes);
-            distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta, cli.verbose);
+        (None, Some(scheme)) => scheme.colors().iter().map(|c| c.to_string()).collect(),
+        (None, None) => Vec::new(),
+    };
+    // a random palette has its own generator seeded from the seed, so the generated colors only depend
+    // on the seed and not on how many random numbers the algorithm and the trials drew before
+    let mut palette_rng = StdRng::seed_from_u64(cli.seed.unwrap());
+    let unique_colors = generate_colors(num_colors, &palette, cli.random_palette, &mut palette_rng);
+    let logger = logger(cli);
+    log_message!(logger.as_ref(), LogLevel::Debug, "colors generated = {:#?}", unique_colors);
+
+    if let Some(dotfile) = &cli.dotfile {
+        let options = DotOptions {
+            labels: cli.labels,
+            append: cli.append,
+            id_offset: usize::from(cli.one_indexed),
+        };
+        graph_to_dot(dotfile, graph, nodes, &unique_colors, &options, logger.as_ref()).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+    }
 
-            for node in nodes.iter_mut() {
-                println!("node {:3} has permanent color {:3}", node.id, node.coloring.color());
-            }
+    if let Some(graphml) = &cli.graphml {
+        graph_to_graphml(graphml, graph, nodes, &unique_colors, logger.as_ref());
+    }
+
+    if let Some(svg) = &cli.svg {
+        graph_to_svg(svg, graph, nodes, &unique_colors, usize::from(cli.one_indexed), logger.as_ref());
+    }
+}
+
+fn main() {
+    let mut cli = Cli::parse();
+    // the banner and other informational output is only printed in human format and if not quiet,
+    // a scaling sweep always prints CSV so it's left out there as well
+    let informational = cli.format == Format::Human && !cli.quiet && cli.scaling_sweep.is_empty();
+    let family = generators().get(&cli.mode).copied();
+    let num_nodes = match family.map(|f| f.size) {
+        Some(GraphSize::Options(num_nodes)) => num_nodes(&cli),
+        _ => cli.num as usize,
+    };
+    if informational {
+        match family.map(|f| f.size) {
+            _ if cli.compare_modes => println!("Comparing run modes with {} vertices", cli.num),
+            Some(GraphSize::Loaded) => println!("Running in {:?} mode with input '{}'", cli.mode, cli.input.as_ref().unwrap()),
+            _ => println!("Running in {:?} mode with {num_nodes} vertices", cli.mode),
+        }
+    }
+
+    // use the given seed or choose one from entropy, it's printed so the run can be replayed
+    let random_seed = cli.seed.is_none();
+    let seed = cli.seed.unwrap_or_else(|| thread_rng().gen());
+    cli.seed = Some(seed);
+    if informational {
+        println!("Using seed {seed}");
+    } else if random_seed && cli.format != Format::Json && cli.scaling_sweep.is_empty() {
+        // a random seed would be lost without the banner, the JSON output contains it and a sweep prints it below
+        eprintln!("Using seed {seed}");
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    if cli.compare_modes {
+        compare_modes(&cli, cli.num as usize, seed);
+        return;
+    }
 
-            if cli.dotfile.is_some() {
-                graph_to_dot(cli.dotfile.unwrap(), graph, &nodes, delta, cli.verbose);
+    if !cli.scaling_sweep.is_empty() {
+        eprintln!("Using seed {seed}");
+        cli.verbose = false;
+        cli.progress = false;
+        let sizes = cli.scaling_sweep.clone();
+        scaling_sweep(&cli, &sizes, seed);
+        return;
+    }
+
+    match cli.mode {
+        RunMode::Verify => {
+            // the random regular graph is left out because it also needs a degree
+            let modes = RunMode::value_variants().iter().filter(|&&m| sized_by_num(m) && m != RunMode::RandomRegular);
+            for &mode in modes {
+                run_test(&cli, mode, num_nodes, seed);
             }
         }
+        RunMode::Testcase => {
+            test_case(&cli, &mut rng);
+        }
+        _ => {
+            let (graph, nodes, delta) = (family.unwrap().build)(&cli, num_nodes, &mut rng);
+            run_algorithm(graph, nodes, delta, &cli, &mut rng);
+        }
     }
 }
@@ -0,0 +1,76 @@
+use std::fs;
+
+use rand::Rng;
+use rand::distributions::Uniform;
+
+/// reads a palette file containing one color per line, e.g. `#ff0000`
+/// the color on line `i` is used for color index `i`, blank lines are skipped
+pub fn load_palette(path: &str) -> Result<Vec<String>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Reading palette '{path}' failed: {e}"))?;
+
+    Ok(content.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| l.to_string())
+        .collect())
+}
+
+/// checks that the palette has a distinct color for each of the color indices `0..num_colors`
+/// returns a warning for a palette which is too short and for every color which is used for two color indices
+pub fn palette_warnings(palette: &[String], num_colors: usize) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if palette.len() < num_colors {
+        warnings.push(format!("it has {} colors but {num_colors} are needed, the {} additional colors are generated",
+                              palette.len(), num_colors - palette.len()));
+    }
+
+    for (i, color) in palette.iter().enumerate().take(num_colors) {
+        if let Some(j) = palette[..i].iter().position(|c| c.eq_ignore_ascii_case(color)) {
+            warnings.push(format!("the color {color} is used for both color {j} and color {i}"));
+        }
+    }
+
+    warnings
+}
+
+/// converts a color given by hue, saturation and value, all in the range [0, 1], to a hex color like `#ff0000`
+pub fn hsv_to_hex(hue: f64, saturation: f64, value: f64) -> String {
+    let sector = (hue.fract() * 6.0).floor();
+    let f = hue.fract() * 6.0 - sector;
+    let p = value * (1.0 - saturation);
+    let q = value * (1.0 - f * saturation);
+    let t = value * (1.0 - (1.0 - f) * saturation);
+
+    let (r, g, b) = match sector as u8 {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    };
+
+    let to_byte = |c: f64| (c * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", to_byte(r), to_byte(g), to_byte(b))
+}
+
+/// returns the colors used to draw the color indices `0..num_colors`
+/// the colors are taken from the palette, if it doesn't have enough colors the remaining ones are generated,
+/// color `i` gets the hue `i / num_colors` so all colors are as distinct as possible,
+/// with `random` the colors are sampled randomly instead
+pub fn generate_colors(num_colors: usize, palette: &[String], random: bool, rng: &mut impl Rng) -> Vec<String> {
+    (0..num_colors).map(|i| {
+        if let Some(color) = palette.get(i) {
+            return color.clone();
+        }
+
+        if !random {
+            return hsv_to_hex(i as f64 / num_colors as f64, 0.65, 0.85);
+        }
+
+        let dist = Uniform::new(0, 200);
+        format!("#{:02x}{:02x}{:02x}", rng.sample(dist), rng.sample(dist), rng.sample(dist))
+    }).collect()
+}
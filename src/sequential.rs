@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashSet};
+
+use rs_graph::VecGraph;
+
+use crate::{Color, neighbors, Node};
+use crate::Coloring::Permanent;
+
+/// returns the colors of the nodes where only the precolored nodes are colored
+fn initial_colors(num_nodes: usize, precolored: &[(usize, Color)]) -> Vec<Option<Color>> {
+    let mut colors = vec![None; num_nodes];
+    for &(v, color) in precolored {
+        colors[v] = Some(color);
+    }
+    colors
+}
+
+/// colors the graph sequentially in the order of the node ids
+/// each node gets the smallest color that isn't used by an already colored neighbor
+/// this uses at most delta + 1 colors and gives a deterministic baseline for the randomized algorithm
+/// the precolored nodes given as `(id, color)` keep their color and are colored first
+/// afterwards every node has a permanent color
+pub fn greedy_coloring(graph: &VecGraph, nodes: &mut [Node], delta: usize, precolored: &[(usize, Color)]) {
+    let neighbors = neighbors(graph, nodes.len());
+    let mut colors = initial_colors(nodes.len(), precolored);
+
+    for node in nodes.iter_mut() {
+        if let Some(color) = colors[node.id] {
+            node.coloring = Permanent(color);
+            continue;
+        }
+
+        // a precolored neighbor may have a color larger than delta, which doesn't take any of the delta + 1 colors
+        let mut used = vec![false; delta + 1];
+        for c in neighbors[node.id].iter().filter_map(|&u| colors[u]).filter(|&c| c <= delta) {
+            used[c] = true;
+        }
+
+        // a node has at most delta neighbors, so one of the delta + 1 colors is always free
+        let color = used.iter().position(|u| !u).expect("no free color left, delta is too small");
+        colors[node.id] = Some(color);
+        node.coloring = Permanent(color);
+    }
+}
+
+/// colors the graph using the DSATUR heuristic
+/// repeatedly the uncolored node with the most distinct colors among its neighbors (saturation) is chosen,
+/// ties are broken by the higher degree and then the lower id
+/// the chosen node gets the smallest color that isn't used by a neighbor, this uses at most delta + 1 colors
+/// the precolored nodes given as `(id, color)` keep their color and count towards the saturation of their neighbors
+/// afterwards every node has a permanent color
+pub fn dsatur_coloring(graph: &VecGraph, nodes: &mut [Node], delta: usize, precolored: &[(usize, Color)]) {
+    let neighbors = neighbors(graph, nodes.len());
+    let mut colors = initial_colors(nodes.len(), precolored);
+    let mut neighbor_colors: Vec<HashSet<Color>> = vec![HashSet::new(); nodes.len()];
+    for &(v, color) in precolored {
+        for &u in &neighbors[v] {
+            neighbor_colors[u].insert(color);
+        }
+    }
+
+    for _ in 0..nodes.len() - colors.iter().flatten().count() {
+        let v = (0..nodes.len())
+            .filter(|&v| colors[v].is_none())
+            .max_by_key(|&v| (neighbor_colors[v].len(), neighbors[v].len(), std::cmp::Reverse(v)))
+            .unwrap();
+
+        // a node has at most delta neighbors, so one of the delta + 1 colors is always free
+        let color = (0..=delta).find(|c| !neighbor_colors[v].contains(c))
+            .expect("no free color left, delta is too small");
+        colors[v] = Some(color);
+
+        for &u in &neighbors[v] {
+            neighbor_colors[u].insert(color);
+        }
+    }
+
+    for node in nodes.iter_mut() {
+        node.coloring = Permanent(colors[node.id].unwrap());
+    }
+}
+
+/// improves a coloring after the algorithm converged by moving every node in the order of the node ids
+/// to the smallest color that is already used by some node, smaller than its own and not used by a neighbor
+/// nodes only move to colors which are still in use, so the number of colors can only decrease
+/// and a valid coloring stays valid, a color is dropped once all of its nodes have moved away
+pub fn recolor_reduce(graph: &VecGraph, nodes: &mut [Node]) {
+    let neighbors = neighbors(graph, nodes.len());
+    let mut class_sizes: BTreeMap<Color, usize> = BTreeMap::new();
+    for node in nodes.iter() {
+        *class_sizes.entry(*node.coloring.color()).or_default() += 1;
+    }
+
+    for v in 0..nodes.len() {
+        let current = *nodes[v].coloring.color();
+        let taken: HashSet<Color> = neighbors[v].iter().map(|&u| *nodes[u].coloring.color()).collect();
+        let Some(color) = class_sizes.range(..current).map(|(&c, _)| c).find(|c| !taken.contains(c)) else {
+            continue;
+        };
+
+        *class_sizes.get_mut(&current).unwrap() -= 1;
+        if class_sizes[&current] == 0 {
+            class_sizes.remove(&current);
+        }
+        *class_sizes.get_mut(&color).unwrap() += 1;
+        nodes[v].coloring = Permanent(color);
+    }
+}
@@ -0,0 +1,412 @@
+//! runs the algorithm on the different graph types with fixed seeds and checks the resulting colorings
+//! run with `cargo test`
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rs_graph::VecGraph;
+
+use color_reduction::{AlgorithmOptions, color_histogram, Coloring, ColoringError, colors_used, distributed_randomized_coloring_algorithm, expected_rounds, find_conflicts, Node, round_distribution, Schedule};
+use color_reduction::generators::{caterpillar, chain, complete_graph, cycle, disjoint_union, grid, hydrocarbon, hypercube, kary_tree, mycielski, petersen, random_graph, torus, wheel, windmill};
+use color_reduction::input::graph_from_edge_pairs;
+use color_reduction::logger::{LogLevel, Logger};
+use color_reduction::sequential::{dsatur_coloring, greedy_coloring, recolor_reduce};
+
+const SEEDS: u64 = 5;
+
+/// runs the algorithm with both schedules and several seeds on the graph
+/// every run must converge to a valid coloring using at most delta + 1 colors
+fn assert_valid_coloring((graph, nodes, delta): (VecGraph, Vec<Node>, usize)) {
+    for schedule in [Schedule::Synchronous, Schedule::Asynchronous] {
+        let options = AlgorithmOptions {
+            schedule,
+            ..AlgorithmOptions::default()
+        };
+
+        for seed in 0..SEEDS {
+            let mut nodes = nodes.clone();
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut rng)
+                .unwrap_or_else(|e| panic!("{schedule:?} run with seed {seed} did not converge: {e}"));
+
+            assert!(find_conflicts(&graph, &nodes).is_empty(), "{schedule:?} run with seed {seed} is not valid");
+            assert!(nodes.iter().all(|n| *n.coloring.color() <= delta), "{schedule:?} run with seed {seed} used more than delta + 1 colors");
+        }
+    }
+}
+
+#[test]
+fn complete_graph_is_colored_validly() {
+    assert_valid_coloring(complete_graph(50));
+}
+
+#[test]
+fn chain_is_colored_validly() {
+    assert_valid_coloring(chain(1000));
+}
+
+#[test]
+fn cycle_is_colored_validly() {
+    assert_valid_coloring(cycle(999));
+}
+
+#[test]
+fn grid_is_colored_validly() {
+    assert_valid_coloring(grid(20, 30));
+}
+
+#[test]
+fn disjoint_complete_graphs_are_colored_validly() {
+    // the algorithm must not assume the graph is connected, both components can reuse the same colors
+    let (first, _, _) = complete_graph(20);
+    let (second, _, _) = complete_graph(30);
+    let (graph, nodes, delta) = disjoint_union(&first, &second);
+    assert_eq!(nodes.len(), 50);
+    assert_eq!(delta, 29);
+
+    assert_valid_coloring((graph, nodes, delta));
+}
+
+#[test]
+fn isolated_vertices_are_permanent_after_the_first_round() {
+    // a clique on the even vertices 0 to 6 and a chain 6 - 8 - 10, the odd vertices are isolated
+    let (graph, nodes, delta) = graph_from_edge_pairs(&[(0, 2), (0, 4), (0, 6), (2, 4), (2, 6), (4, 6), (6, 8), (8, 10)]);
+
+    for schedule in [Schedule::Synchronous, Schedule::Asynchronous] {
+        let options = AlgorithmOptions {
+            schedule,
+            max_rounds: 1,
+            ..AlgorithmOptions::default()
+        };
+
+        for seed in 0..SEEDS {
+            let mut nodes = nodes.clone();
+            let result = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(seed));
+            let stats = match &result {
+                Ok(stats) => stats,
+                Err(e) => e.stats(),
+            };
+
+            assert_eq!(stats.rounds, 1);
+            for node in nodes.iter().filter(|n| n.id % 2 == 1) {
+                assert!(matches!(node.coloring, Coloring::Permanent(_)),
+                        "{schedule:?} run with seed {seed}: isolated node {} is still a candidate", node.id);
+            }
+        }
+    }
+}
+
+#[test]
+fn complete_graph_uses_every_color_once() {
+    let (graph, mut nodes, delta) = complete_graph(50);
+    distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut StdRng::seed_from_u64(0))
+        .unwrap();
+
+    let histogram = color_histogram(&nodes);
+    assert_eq!(histogram.len(), delta + 1);
+    assert!(histogram.iter().all(|&(_, count)| count == 1));
+}
+
+#[test]
+fn zero_timeout_stops_before_the_first_round() {
+    let (graph, mut nodes, delta) = chain(100);
+    let options = AlgorithmOptions {
+        timeout: Some(Duration::ZERO),
+        ..AlgorithmOptions::default()
+    };
+
+    match distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(0)) {
+        Err(ColoringError::Timeout(stats)) => assert_eq!(stats.rounds, 0),
+        other => panic!("expected a timeout but got {other:?}"),
+    }
+}
+
+#[test]
+fn rounds_stay_below_a_high_probability_bound() {
+    // at most n * (3/4)^r candidates are expected to be left after the expected rounds r plus one,
+    // so by Markov's inequality a run needs more than log_{4/3}(1000) additional rounds with probability at most 1/1000
+    let slack = 1000f64.ln() / (4.0f64 / 3.0).ln();
+
+    for (graph, nodes, delta) in [complete_graph(100), cycle(1000), grid(30, 30)] {
+        let bound = expected_rounds(nodes.len(), delta) + slack;
+
+        for seed in 0..SEEDS {
+            let mut nodes = nodes.clone();
+            let stats = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut StdRng::seed_from_u64(seed))
+                .unwrap();
+            assert!(stats.rounds as f64 <= bound, "seed {seed} took {} rounds, more than {bound:.1}", stats.rounds);
+        }
+    }
+}
+
+#[test]
+fn algorithm_stops_once_the_priority_nodes_are_permanent() {
+    let (graph, nodes, delta) = grid(20, 20);
+    let options = AlgorithmOptions {
+        priority_nodes: vec![0, 57, 399],
+        ..AlgorithmOptions::default()
+    };
+
+    for seed in 0..SEEDS {
+        let mut full_nodes = nodes.clone();
+        let full = distributed_randomized_coloring_algorithm(&graph, &mut full_nodes, delta + 1, &AlgorithmOptions::default(), &mut StdRng::seed_from_u64(seed))
+            .unwrap();
+
+        let mut nodes = nodes.clone();
+        let stats = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(seed))
+            .unwrap();
+
+        assert!(stats.rounds <= full.rounds, "seed {seed} took longer with priority nodes");
+        for v in [0, 57, 399] {
+            assert!(matches!(nodes[v].coloring, Coloring::Permanent(_)), "seed {seed}: priority node {v} is still a candidate");
+        }
+    }
+}
+
+#[test]
+fn missing_priority_node_is_an_error() {
+    let (graph, mut nodes, delta) = chain(10);
+    let options = AlgorithmOptions { priority_nodes: vec![2, 10], ..AlgorithmOptions::default() };
+    let result = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(0));
+    assert!(matches!(result, Err(ColoringError::InvalidOptions(_))));
+}
+
+#[test]
+fn precolored_nodes_keep_their_color() {
+    let (graph, nodes, delta) = cycle(101);
+    let precolored = vec![(0, 2), (50, 0), (100, 1)];
+
+    for schedule in [Schedule::Synchronous, Schedule::Asynchronous] {
+        let options = AlgorithmOptions {
+            schedule,
+            precolored: precolored.clone(),
+            ..AlgorithmOptions::default()
+        };
+
+        for seed in 0..SEEDS {
+            let mut nodes = nodes.clone();
+            distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(seed))
+                .unwrap();
+
+            assert!(find_conflicts(&graph, &nodes).is_empty(), "{schedule:?} run with seed {seed} is not valid");
+            for &(v, color) in &precolored {
+                assert!(matches!(nodes[v].coloring, Coloring::Permanent(c) if c == color), "{schedule:?} run with seed {seed} recolored node {v}");
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid_precoloring_is_an_error() {
+    let (graph, nodes, delta) = cycle(10);
+
+    for precolored in [vec![(10, 0)], vec![(3, delta + 1)], vec![(3, 0), (3, 1)]] {
+        let options = AlgorithmOptions { precolored: precolored.clone(), ..AlgorithmOptions::default() };
+        let mut nodes = nodes.clone();
+        let result = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(0));
+        assert!(matches!(result, Err(ColoringError::InvalidOptions(_))), "the precoloring {precolored:?} was accepted");
+    }
+}
+
+#[test]
+fn sequential_algorithms_keep_the_precolored_nodes() {
+    // node 5 has a color larger than delta, which the other nodes can't use anyway
+    let (graph, nodes, delta) = grid(6, 6);
+    let precolored = vec![(0, 1), (5, 7), (7, 1), (14, 0)];
+
+    for (name, color) in [("greedy", greedy_coloring as fn(&_, &mut _, _, &_)), ("dsatur", dsatur_coloring)] {
+        let mut nodes = nodes.clone();
+        color(&graph, &mut nodes, delta, &precolored);
+
+        assert!(find_conflicts(&graph, &nodes).is_empty(), "{name} coloring is not valid");
+        for &(v, c) in &precolored {
+            assert_eq!(*nodes[v].coloring.color(), c, "{name} recolored node {v}");
+        }
+        assert!(nodes.iter().filter(|n| n.id != 5).all(|n| *n.coloring.color() <= delta), "{name} used more than delta + 1 colors");
+    }
+}
+
+#[test]
+fn single_node_is_permanent_after_one_round() {
+    for (graph, mut nodes, delta) in [complete_graph(1), chain(1), cycle(1), grid(1, 1)] {
+        let stats = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut StdRng::seed_from_u64(0))
+            .unwrap();
+
+        assert_eq!(stats.rounds, 1);
+        assert!(matches!(nodes[0].coloring, Coloring::Permanent(0)));
+    }
+}
+
+#[test]
+fn round_distribution_matches_single_runs() {
+    let (graph, nodes, delta) = cycle(500);
+    let rounds = round_distribution(&graph, &nodes, delta, 0..SEEDS);
+    assert_eq!(rounds.len(), SEEDS as usize);
+
+    for (seed, &r) in (0..SEEDS).zip(&rounds) {
+        let mut nodes = nodes.clone();
+        let stats = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut StdRng::seed_from_u64(seed))
+            .unwrap();
+        assert_eq!(stats.rounds, r, "seed {seed}");
+    }
+}
+
+#[test]
+fn recolor_reduce_never_needs_more_colors() {
+    // a path colored 0, 5, 9 only needs the colors 0 and 5
+    let (graph, mut nodes, _) = chain(3);
+    for (node, color) in nodes.iter_mut().zip([0, 5, 9]) {
+        node.coloring = Coloring::Permanent(color);
+    }
+    recolor_reduce(&graph, &mut nodes);
+    let colors: Vec<usize> = nodes.iter().map(|n| *n.coloring.color()).collect();
+    assert_eq!(colors, [0, 5, 0]);
+
+    let (graph, nodes, delta) = grid(10, 10);
+    for seed in 0..SEEDS {
+        let mut nodes = nodes.clone();
+        distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &AlgorithmOptions::default(), &mut StdRng::seed_from_u64(seed)).unwrap();
+        let before = colors_used(&nodes);
+
+        recolor_reduce(&graph, &mut nodes);
+        assert!(find_conflicts(&graph, &nodes).is_empty(), "seed {seed} is not valid after reducing");
+        assert!(colors_used(&nodes) <= before, "seed {seed} uses more colors after reducing");
+    }
+}
+
+#[test]
+fn never_more_than_delta_plus_one_colors_are_used() {
+    assert_valid_coloring(hydrocarbon(100));
+    assert_valid_coloring(wheel(40));
+    assert_valid_coloring(windmill(15));
+    assert_valid_coloring(kary_tree(100, 3));
+    assert_valid_coloring(caterpillar(20, 4));
+    assert_valid_coloring(torus(8, 9));
+    assert_valid_coloring(hypercube(6));
+    assert_valid_coloring(petersen());
+    assert_valid_coloring(mycielski(6));
+    assert_valid_coloring(random_graph(150, 0.3, &mut StdRng::seed_from_u64(7)));
+}
+
+#[test]
+fn dropped_messages_only_delay_the_coloring() {
+    let (graph, nodes, delta) = grid(10, 10);
+
+    for schedule in [Schedule::Synchronous, Schedule::Asynchronous] {
+        for seed in 0..SEEDS {
+            let mut nodes = nodes.clone();
+            let options = AlgorithmOptions {
+                schedule,
+                drop_prob: 0.3,
+                ..AlgorithmOptions::default()
+            };
+
+            let stats = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(seed))
+                .unwrap_or_else(|e| panic!("{schedule:?} run with seed {seed} did not converge: {e}"));
+            assert!(stats.dropped > 0, "{schedule:?} run with seed {seed} didn't drop any messages");
+            assert!(find_conflicts(&graph, &nodes).is_empty(), "{schedule:?} run with seed {seed} is not valid");
+        }
+    }
+}
+
+#[test]
+fn high_color_bias_still_converges() {
+    let options = AlgorithmOptions {
+        color_bias: 0.95,
+        ..AlgorithmOptions::default()
+    };
+
+    for (name, (graph, nodes, delta)) in [("chain", chain(3)), ("complete graph", complete_graph(20)), ("grid", grid(10, 10))] {
+        for seed in 0..SEEDS {
+            let mut nodes = nodes.clone();
+            distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(seed))
+                .unwrap_or_else(|e| panic!("{name} with seed {seed} did not converge: {e}"));
+            assert!(find_conflicts(&graph, &nodes).is_empty(), "{name} with seed {seed} is not valid");
+        }
+    }
+}
+
+#[test]
+fn long_chain_fixes_every_node_in_exactly_one_round() {
+    let (graph, nodes, delta) = chain(10000);
+    let degree = |v: usize| if v == 0 || v == nodes.len() - 1 { 1 } else { 2 };
+    let run = |max_rounds| {
+        let mut nodes = nodes.clone();
+        let options = AlgorithmOptions { max_rounds, ..AlgorithmOptions::default() };
+        let result = distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(0));
+        // the inboxes hold the messages of the last round, no node may receive more than one message per neighbor
+        for node in &nodes {
+            assert!(node.inbox().len() <= degree(node.id), "node {} received {} messages after {max_rounds} rounds", node.id, node.inbox().len());
+        }
+        (nodes, result)
+    };
+
+    let (nodes, result) = run(AlgorithmOptions::default().max_rounds);
+    let stats = result.unwrap_or_else(|e| panic!("Algorithm did not converge: {e}"));
+    assert!(find_conflicts(&graph, &nodes).is_empty());
+    assert_eq!(stats.newly_fixed.len(), stats.rounds);
+    assert_eq!(stats.newly_fixed.iter().sum::<usize>(), nodes.len());
+
+    // the chain takes many rounds, stopping early also checks the inboxes in the middle of the run
+    for max_rounds in [1, 2, stats.rounds / 2] {
+        let _ = run(max_rounds);
+    }
+}
+
+#[test]
+fn nodes_in_a_set_are_identified_by_their_id() {
+    let mut set: HashSet<Node> = (0..10).map(Node::new).collect();
+    assert_eq!(set.len(), 10);
+    assert!(set.contains(&Node::new(3)));
+    assert!(!set.contains(&Node::new(10)));
+
+    // a node with the same id but a different color is the same node
+    let mut recolored = Node::new(3);
+    recolored.coloring = Coloring::Permanent(7);
+    assert!(set.contains(&recolored));
+    assert!(!set.insert(recolored));
+    assert_eq!(set.len(), 10);
+}
+
+#[test]
+fn inbox_shows_why_a_node_went_permanent() {
+    for schedule in [Schedule::Synchronous, Schedule::Asynchronous] {
+        let (graph, mut nodes, delta) = complete_graph(10);
+        let options = AlgorithmOptions {
+            schedule,
+            ..AlgorithmOptions::default()
+        };
+        distributed_randomized_coloring_algorithm(&graph, &mut nodes, delta + 1, &options, &mut StdRng::seed_from_u64(0)).unwrap();
+
+        // the inbox is kept from the round in which the node went permanent, no neighbor had its color then
+        for node in &nodes {
+            assert_eq!(node.inbox().len(), delta, "{schedule:?}: node {} kept the wrong inbox", node.id);
+            assert!(node.inbox().colors().all(|c| c != *node.coloring.color()), "{schedule:?}: node {} has a color used by a neighbor", node.id);
+        }
+    }
+}
+
+#[test]
+fn logger_only_receives_the_enabled_levels() {
+    let (graph, nodes, delta) = chain(10);
+
+    for max_level in [LogLevel::Info, LogLevel::Debug, LogLevel::Trace] {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        let options = AlgorithmOptions {
+            logger: Some(Logger::new(max_level, move |level, message| sink.lock().unwrap().push((level, message.to_string())))),
+            ..AlgorithmOptions::default()
+        };
+        distributed_randomized_coloring_algorithm(&graph, &mut nodes.clone(), delta + 1, &options, &mut StdRng::seed_from_u64(0)).unwrap();
+
+        let received = received.lock().unwrap();
+        assert!(received.iter().all(|&(level, _)| level <= max_level), "{max_level:?} logger received a message of a higher level");
+        for level in [LogLevel::Info, LogLevel::Debug, LogLevel::Trace].into_iter().filter(|&l| l <= max_level) {
+            assert!(received.iter().any(|&(l, _)| l == level), "{max_level:?} logger didn't receive any {level:?} message");
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! checks the size and max degree of generated graphs, especially for very small sizes
+//! run with `cargo test`
+
+use std::{env, fs, process};
+use std::process::Command;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rs_graph::VecGraph;
+use rs_graph::traits::FiniteGraph;
+
+use color_reduction::{clique_lower_bound, degrees, max_degree, Node};
+use color_reduction::builder::GraphBuilder;
+use color_reduction::generators::{barbell, caterpillar, chain, clustered, complement, complete_graph, cycle, disjoint_union, generalized_petersen, grid, hydrocarbon, hypercube, kary_tree, king_grid, mycielski, petersen, random_graph, random_regular, torus, wheel, windmill};
+
+#[test]
+fn hydrocarbon_with_one_node_has_no_edges() {
+    let (graph, nodes, delta) = hydrocarbon(1);
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(graph.num_edges(), 0);
+    assert_eq!(delta, 0);
+}
+
+#[test]
+fn hydrocarbon_with_two_nodes_has_one_bond() {
+    let (graph, nodes, delta) = hydrocarbon(2);
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(graph.num_edges(), 1);
+    assert_eq!(delta, 1);
+}
+
+#[test]
+fn hydrocarbon_with_three_nodes_has_two_bonds() {
+    let (graph, nodes, delta) = hydrocarbon(3);
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(graph.num_edges(), 2);
+    assert_eq!(delta, 2);
+}
+
+#[test]
+fn hydrocarbon_is_a_tree_with_correct_delta() {
+    for n in 1..50 {
+        let (graph, _, delta) = hydrocarbon(n);
+        assert_eq!(graph.num_edges(), n - 1, "hydrocarbon with {n} nodes is not a tree");
+        assert_eq!(max_degree(&graph), delta, "hydrocarbon with {n} nodes has the wrong delta");
+    }
+}
+
+#[test]
+fn caterpillar_is_a_tree_with_correct_delta() {
+    let (graph, nodes, delta) = caterpillar(5, 3);
+    assert_eq!(nodes.len(), 20);
+    assert_eq!(graph.num_edges(), 19);
+    assert_eq!(delta, 5);
+
+    // a single spine vertex is a star
+    let (graph, nodes, delta) = caterpillar(1, 4);
+    assert_eq!(nodes.len(), 5);
+    assert_eq!(max_degree(&graph), 4);
+    assert_eq!(delta, 4);
+}
+
+#[test]
+fn every_undirected_edge_is_stored_once() {
+    for n in 1..30 {
+        let (graph, _, delta) = complete_graph(n);
+        assert_eq!(graph.num_edges(), n * (n - 1) / 2, "complete graph with {n} nodes");
+        assert_eq!(max_degree(&graph), delta);
+
+        let (graph, _, delta) = chain(n);
+        assert_eq!(graph.num_edges(), n - 1, "chain with {n} nodes");
+        assert_eq!(max_degree(&graph), delta);
+    }
+
+    for n in 3..30 {
+        let (graph, _, delta) = cycle(n);
+        assert_eq!(graph.num_edges(), n, "cycle with {n} nodes");
+        assert_eq!(max_degree(&graph), delta);
+    }
+
+    // every row has cols - 1 edges and every column rows - 1 edges
+    let (graph, _, delta) = grid(4, 7);
+    assert_eq!(graph.num_edges(), 4 * 6 + 7 * 3);
+    assert_eq!(max_degree(&graph), delta);
+
+    // the king grid additionally has two diagonals in each of the 3 * 6 squares
+    let (graph, _, delta) = king_grid(4, 7);
+    assert_eq!(graph.num_edges(), 4 * 6 + 7 * 3 + 2 * 3 * 6);
+    assert_eq!(max_degree(&graph), delta);
+}
+
+#[test]
+fn king_grid_needs_four_colors() {
+    // every 2x2 square is a clique of size 4 and an interior vertex has 8 neighbors
+    let (graph, _, delta) = king_grid(5, 5);
+    assert_eq!(delta, 8);
+    assert_eq!(clique_lower_bound(&graph), 4);
+
+    let (graph, _, delta) = king_grid(1, 5);
+    assert_eq!(delta, 2);
+    assert_eq!(clique_lower_bound(&graph), 2);
+}
+
+/// checks that the delta returned by a generator is the max degree of the graph it created
+fn assert_delta(name: &str, (graph, nodes, delta): (VecGraph, Vec<Node>, usize)) {
+    assert_eq!(nodes.len(), graph.num_nodes(), "{name} has a different number of nodes than vertices");
+    assert_eq!(max_degree(&graph), delta, "{name} declares the wrong delta");
+}
+
+#[test]
+fn generators_declare_the_correct_delta() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for n in 1..50 {
+        assert_delta(&format!("complete graph with {n} nodes"), complete_graph(n));
+        assert_delta(&format!("chain with {n} nodes"), chain(n));
+        assert_delta(&format!("cycle with {n} nodes"), cycle(n));
+        assert_delta(&format!("hydrocarbon with {n} nodes"), hydrocarbon(n));
+        assert_delta(&format!("wheel with {n} nodes"), wheel(n));
+        assert_delta(&format!("random graph with {n} nodes"), random_graph(n, 0.3, &mut rng));
+        for d in (0..n.min(8)).filter(|d| n * d % 2 != 1) {
+            assert_delta(&format!("random {d}-regular graph with {n} nodes"), random_regular(n, d, &mut rng));
+        }
+
+        for k in 1..5 {
+            assert_delta(&format!("{k}-ary tree with {n} nodes"), kary_tree(n, k));
+            assert_delta(&format!("caterpillar with {n} spine nodes and {k} leaves"), caterpillar(n, k));
+        }
+    }
+
+    for rows in 1..8 {
+        for cols in 1..8 {
+            assert_delta(&format!("{rows}x{cols} grid"), grid(rows, cols));
+            assert_delta(&format!("{rows}x{cols} king grid"), king_grid(rows, cols));
+            assert_delta(&format!("{rows}x{cols} torus"), torus(rows, cols));
+        }
+    }
+
+    for d in 0..8 {
+        assert_delta(&format!("hypercube of dimension {d}"), hypercube(d));
+    }
+
+    for k in 1..8 {
+        assert_delta(&format!("Mycielski graph M_{k}"), mycielski(k));
+        assert_delta(&format!("windmill graph F_{k}"), windmill(k));
+        for path_len in 0..4 {
+            assert_delta(&format!("barbell graph of {k}-cliques with a path of {path_len}"), barbell(k, path_len));
+        }
+    }
+
+    assert_delta("petersen graph", petersen());
+    for n in 3..12 {
+        for k in (1..n).filter(|k| 2 * k < n) {
+            assert_delta(&format!("generalized petersen graph GP({n}, {k})"), generalized_petersen(n, k));
+        }
+    }
+
+    for (clusters, size, bridges) in [(1, 5, 3), (3, 4, 0), (5, 6, 10), (2, 2, 100)] {
+        assert_delta(&format!("{clusters} clusters of size {size} with {bridges} bridges"), clustered(clusters, size, bridges, &mut rng));
+    }
+    assert_delta("petersen graph next to a 5x5 grid", disjoint_union(&petersen().0, &grid(5, 5).0));
+    assert_delta("isolated vertex next to a complete graph", disjoint_union(&complete_graph(1).0, &complete_graph(6).0));
+}
+
+#[test]
+fn mycielski_graphs_have_the_expected_size_and_no_triangles() {
+    // number of vertices and edges of M_1 to M_6
+    let sizes = [(1, 0), (2, 1), (5, 5), (11, 20), (23, 71), (47, 236)];
+
+    for (k, &(num_nodes, num_edges)) in (1..).zip(sizes.iter()) {
+        let (graph, _, _) = mycielski(k);
+        assert_eq!(graph.num_nodes(), num_nodes, "M_{k} has the wrong number of vertices");
+        assert_eq!(graph.num_edges(), num_edges, "M_{k} has the wrong number of edges");
+        assert_eq!(clique_lower_bound(&graph), k.min(2), "M_{k} contains a triangle");
+    }
+}
+
+#[test]
+fn random_regular_graphs_are_regular() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for (n, d) in [(10, 3), (100, 4), (51, 6), (200, 10), (8, 7)] {
+        let (graph, _, delta) = random_regular(n, d, &mut rng);
+        assert_eq!(delta, d);
+        assert_eq!(graph.num_edges(), n * d / 2, "random {d}-regular graph with {n} nodes");
+        assert!(degrees(&graph).iter().all(|&degree| degree == d), "random {d}-regular graph with {n} nodes");
+    }
+}
+
+#[test]
+fn complement_has_the_missing_edges() {
+    let (graph, _, _) = complete_graph(10);
+    let (graph, nodes, delta) = complement(&graph);
+    assert_eq!(nodes.len(), 10);
+    assert_eq!(graph.num_edges(), 0);
+    assert_eq!(delta, 0);
+
+    // the complement of the Petersen graph has 45 - 15 edges and every vertex has 9 - 3 neighbors
+    let (graph, _, _) = petersen();
+    assert_delta("complement of the petersen graph", complement(&graph));
+    let (graph, _, delta) = complement(&graph);
+    assert_eq!(graph.num_edges(), 30);
+    assert_eq!(delta, 6);
+}
+
+#[test]
+fn clusters_are_complete_and_connected_by_bridges() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let (graph, nodes, _) = clustered(4, 5, 7, &mut rng);
+    assert_eq!(nodes.len(), 20);
+    assert_eq!(graph.num_edges(), 4 * 10 + 7);
+    assert_eq!(clique_lower_bound(&graph), 5);
+
+    // two clusters of two vertices only have four possible bridges
+    let (graph, _, _) = clustered(2, 2, 100, &mut rng);
+    assert_eq!(graph.num_edges(), 2 + 4);
+}
+
+#[test]
+fn builder_tracks_nodes_and_delta() {
+    let mut builder = GraphBuilder::new(4);
+    builder.add_edge(0, 1);
+    builder.add_edge(0, 2);
+    assert_eq!(builder.delta(), 2);
+
+    let leaf = builder.add_node();
+    assert_eq!(leaf, 4);
+    builder.add_edge(0, leaf);
+    assert_eq!(builder.num_nodes(), 5);
+
+    let (graph, nodes, delta) = builder.build();
+    assert_eq!(max_degree(&graph), delta);
+    assert_eq!(nodes.len(), 5);
+    assert_eq!(graph.num_edges(), 3);
+    assert_eq!(delta, 3);
+}
+
+#[test]
+fn generators_handle_a_single_node_and_no_nodes() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for n in [0, 1] {
+        let graphs = [
+            ("complete graph", complete_graph(n)),
+            ("chain", chain(n)),
+            ("cycle", cycle(n)),
+            ("hydrocarbon", hydrocarbon(n)),
+            ("wheel", wheel(n)),
+            ("tree", kary_tree(n, 2)),
+            ("random graph", random_graph(n, 0.5, &mut rng)),
+            ("random regular graph", random_regular(n, 0, &mut rng)),
+            ("grid", grid(n, n)),
+            ("king grid", king_grid(n, n)),
+            ("torus", torus(n, n)),
+            ("caterpillar", caterpillar(n, 0)),
+            ("clustered", clustered(n, 1, 5, &mut rng)),
+        ];
+
+        for (name, (graph, nodes, delta)) in graphs {
+            assert_eq!(nodes.len(), n, "{name} with {n} nodes");
+            assert_eq!(graph.num_edges(), 0, "{name} with {n} nodes");
+            assert_eq!(delta, 0, "{name} with {n} nodes");
+        }
+    }
+
+    let (graph, nodes, delta) = hypercube(0);
+    assert_eq!((nodes.len(), graph.num_edges(), delta), (1, 0, 0));
+    let (graph, nodes, delta) = mycielski(1);
+    assert_eq!((nodes.len(), graph.num_edges(), delta), (1, 0, 0));
+}
+
+#[test]
+fn generalized_petersen_graphs_are_cubic() {
+    for (n, k) in [(3, 1), (5, 2), (8, 3), (10, 4), (13, 5)] {
+        let (graph, nodes, delta) = generalized_petersen(n, k);
+        assert_eq!(nodes.len(), 2 * n);
+        assert_eq!(graph.num_edges(), 3 * n, "GP({n}, {k})");
+        assert!(degrees(&graph).iter().all(|&d| d == 3), "GP({n}, {k}) is not 3-regular");
+        assert_eq!(delta, 3);
+    }
+}
+
+#[test]
+fn windmill_triangles_share_the_hub() {
+    for k in [1, 2, 5, 20] {
+        let (graph, nodes, delta) = windmill(k);
+        assert_eq!(nodes.len(), 2 * k + 1);
+        assert_eq!(graph.num_edges(), 3 * k);
+        let degrees = degrees(&graph);
+        assert_eq!(degrees[0], 2 * k);
+        assert!(degrees[1..].iter().all(|&d| d == 2), "F_{k} has a blade vertex without degree 2");
+        assert_eq!(delta, 2 * k);
+    }
+}
+
+#[test]
+fn barbell_cliques_are_joined_by_the_path() {
+    for (clique_size, path_len) in [(1, 0), (1, 3), (2, 0), (4, 0), (5, 3), (10, 20)] {
+        let (graph, nodes, delta) = barbell(clique_size, path_len);
+        assert_eq!(nodes.len(), 2 * clique_size + path_len);
+        assert_eq!(graph.num_edges(), clique_size * (clique_size - 1) + path_len + 1, "barbell({clique_size}, {path_len})");
+
+        let degrees = degrees(&graph);
+        assert_eq!(degrees[clique_size - 1], clique_size, "barbell({clique_size}, {path_len})");
+        assert_eq!(degrees[clique_size + path_len], clique_size, "barbell({clique_size}, {path_len})");
+        assert_eq!(delta, clique_size.max(if path_len > 0 { 2 } else { 1 }));
+    }
+}
+
+#[test]
+fn max_degree_of_chain_and_star_is_correct() {
+    // a chain has max degree 2 and a star, a tree where the root has all other nodes as children, has max degree n - 1
+    let (graph, _, delta) = chain(100);
+    assert_eq!(max_degree(&graph), 2);
+    assert_eq!(max_degree(&graph), delta);
+
+    let (graph, _, delta) = kary_tree(100, 99);
+    assert_eq!(max_degree(&graph), 99);
+    assert_eq!(max_degree(&graph), delta);
+}
+
+/// runs the binary with the arguments and returns its stdout, panics if it fails
+fn run_binary(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_color-reduction")).args(args).output().unwrap();
+    assert!(output.status.success(), "{args:?} failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn every_run_mode_builds_a_graph_of_the_announced_size() {
+    // clap lists the run modes when an invalid one is given
+    let output = Command::new(env!("CARGO_BIN_EXE_color-reduction")).args(["-m", "none"]).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let modes = stderr.split("[possible values: ").nth(1).unwrap().split(']').next().unwrap();
+
+    let dir = env::temp_dir().join(format!("color-reduction-generators-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let inputs = [
+        ("file", "0 1\n1 2\n"),
+        ("dimacs", "p edge 3 2\ne 1 2\ne 2 3\n"),
+        ("dot", "graph {\nn0 -- n1\nn1 -- n2\n}\n"),
+        ("matrix", "0 1 0\n1 0 1\n0 1 0\n"),
+    ];
+    for (mode, content) in inputs {
+        fs::write(dir.join(mode), content).unwrap();
+    }
+
+    for mode in modes.split(", ").filter(|&m| m != "testcase" && m != "verify") {
+        let input = dir.join(mode);
+        let args = [
+            "-m", mode, "-s", "1", "--dry-run", "-n", "10", "-g", "3x4", "--dimension", "3", "--outer", "5", "--inner-step", "2",
+            "--clique-size", "3", "--triangles", "2", "--chromatic-number", "4", "--spine", "3", "--edge", "0,3",
+            "--clusters", "2", "--cluster-size", "3", "--degree", "3", "-i", input.to_str().unwrap(),
+        ];
+        let stdout = run_binary(&args);
+
+        let vertices = stdout.lines().find_map(|l| l.strip_prefix("Vertices: "))
+            .unwrap_or_else(|| panic!("{mode} didn't build a graph: {stdout}"));
+        let banner = stdout.lines().next().unwrap();
+        if !banner.contains("with input") {
+            assert!(banner.ends_with(&format!(" with {vertices} vertices")), "{mode}: '{banner}' but the graph has {vertices} vertices");
+        }
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
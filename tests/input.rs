@@ -0,0 +1,118 @@
+//! checks that the input formats are parsed correctly and invalid input is rejected
+//! run with `cargo test`
+
+use rs_graph::traits::FiniteGraph;
+
+use color_reduction::generators::{complete_graph, cycle, disjoint_union, hydrocarbon, petersen, windmill};
+use color_reduction::input::{format_edge_list, graph_from_edge_pairs, parse_adjacency_matrix, parse_coloring, parse_dimacs, parse_edge_list};
+use color_reduction::sequential::greedy_coloring;
+
+#[test]
+fn adjacency_matrix_of_a_path_is_parsed() {
+    let content = "0 1 0\n1 0 1\n\n0 1 0\n";
+    let (graph, nodes, delta) = parse_adjacency_matrix(content).unwrap();
+
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(graph.num_edges(), 2);
+    assert_eq!(delta, 2);
+}
+
+#[test]
+fn adjacency_matrix_must_be_square() {
+    assert!(parse_adjacency_matrix("0 1 0\n1 0 1\n").is_err());
+    assert!(parse_adjacency_matrix("0 1\n1 0 1\n0 1 0\n").is_err());
+}
+
+#[test]
+fn adjacency_matrix_must_be_symmetric() {
+    assert!(parse_adjacency_matrix("0 1 0\n0 0 1\n0 1 0\n").is_err());
+}
+
+#[test]
+fn adjacency_matrix_only_contains_zeros_and_ones() {
+    assert!(parse_adjacency_matrix("0 2\n2 0\n").is_err());
+}
+
+#[test]
+fn edge_pairs_skip_self_loops_and_duplicates() {
+    let (graph, nodes, delta) = graph_from_edge_pairs(&[(0, 1), (1, 2), (2, 0), (1, 0), (3, 3)]);
+
+    assert_eq!(nodes.len(), 4);
+    assert_eq!(graph.num_edges(), 3);
+    assert_eq!(delta, 2);
+}
+
+#[test]
+fn coloring_is_parsed_with_and_without_header() {
+    assert_eq!(parse_coloring("id,color\n0,1\n2,0\n1,2\n", 3, 0), Ok(vec![1, 2, 0]));
+    assert_eq!(parse_coloring("# triangle\n0,0\n\n1,1 # middle\n2,2\n", 3, 0), Ok(vec![0, 1, 2]));
+}
+
+#[test]
+fn coloring_must_color_every_node_once() {
+    assert!(parse_coloring("0,0\n1,1\n", 3, 0).is_err());
+    assert!(parse_coloring("0,0\n1,1\n1,2\n2,0\n", 3, 0).is_err());
+    assert!(parse_coloring("0,0\n1,1\n3,2\n", 3, 0).is_err());
+    assert!(parse_coloring("0,0\n1 1\n2,2\n", 3, 0).is_err());
+}
+
+#[test]
+fn one_indexed_coloring_round_trips() {
+    let (graph, mut nodes, delta) = petersen();
+    greedy_coloring(&graph, &mut nodes, delta, &[]);
+
+    // the same lines as `-f csv --one-indexed`
+    let csv = nodes.iter().fold("id,color\n".to_string(), |csv, n| csv + &format!("{},{}\n", n.id + 1, n.coloring.color()));
+    let colors = nodes.iter().map(|n| *n.coloring.color()).collect::<Vec<_>>();
+    assert_eq!(parse_coloring(&csv, nodes.len(), 1), Ok(colors));
+    assert!(parse_coloring(&csv, nodes.len(), 0).is_err());
+    assert!(parse_coloring("0,0\n1,1\n2,2\n", 3, 1).is_err());
+}
+
+#[test]
+fn exported_edge_list_round_trips() {
+    let graphs = [
+        ("hydrocarbon", hydrocarbon(50)),
+        ("windmill", windmill(4)),
+        ("isolated vertices after a cycle", disjoint_union(&cycle(5).0, &complete_graph(1).0)),
+        ("single vertex", complete_graph(1)),
+    ];
+
+    for (name, (graph, nodes, delta)) in graphs {
+        let content = format_edge_list(&graph);
+        let (loaded, loaded_nodes, loaded_delta) = parse_edge_list(&content).unwrap();
+
+        assert_eq!(loaded_nodes.len(), nodes.len(), "{name}");
+        assert_eq!(loaded_delta, delta, "{name}");
+        assert_eq!(format_edge_list(&loaded), content, "{name}");
+    }
+}
+
+#[test]
+fn edge_list_line_with_one_vertex_adds_it() {
+    let (graph, nodes, delta) = parse_edge_list("0 1\n4\n").unwrap();
+
+    assert_eq!(nodes.len(), 5);
+    assert_eq!(graph.num_edges(), 1);
+    assert_eq!(delta, 1);
+}
+
+#[test]
+fn dimacs_edges_in_both_directions_are_merged() {
+    let content = "c triangle with every edge listed twice\np edge 3 6\ne 1 2\ne 2 1\ne 2 3\ne 3 2\ne 3 1\ne 1 3\n";
+    let (graph, nodes, delta) = parse_dimacs(content).unwrap();
+
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(graph.num_edges(), 3);
+    assert_eq!(delta, 2);
+}
+
+#[test]
+fn edge_list_skips_self_loops_and_duplicates() {
+    let (graph, nodes, delta) = parse_edge_list("0 1\n1 2\n2 2\n1 0\n0 1\n").unwrap();
+
+    // each undirected edge is stored once
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(graph.num_edges(), 2);
+    assert_eq!(delta, 2);
+}
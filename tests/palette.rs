@@ -0,0 +1,41 @@
+//! checks that palettes are validated and filled up with generated colors
+//! run with `cargo test`
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use color_reduction::palette::{generate_colors, hsv_to_hex, palette_warnings};
+
+#[test]
+fn short_palette_with_a_repeated_color_is_reported_and_filled_up() {
+    let palette = vec!["#ff0000".to_string(), "#FF0000".to_string()];
+    let warnings = palette_warnings(&palette, 4);
+    assert_eq!(warnings.len(), 2);
+
+    let colors = generate_colors(4, &palette, false, &mut StdRng::seed_from_u64(0));
+    assert_eq!(colors.len(), 4);
+    assert_eq!(colors[..2], palette[..]);
+}
+
+#[test]
+fn complete_palette_has_no_warnings() {
+    let palette = vec!["#ff0000".to_string(), "#00ff00".to_string(), "#0000ff".to_string()];
+    assert!(palette_warnings(&palette, 3).is_empty());
+    assert_eq!(generate_colors(3, &palette, false, &mut StdRng::seed_from_u64(0)), palette);
+}
+
+#[test]
+fn generated_colors_are_distinct() {
+    let colors = generate_colors(12, &[], false, &mut StdRng::seed_from_u64(0));
+    for (i, color) in colors.iter().enumerate() {
+        assert!(!colors[..i].contains(color), "the color {color} is generated twice");
+    }
+}
+
+#[test]
+fn hsv_primaries_are_converted() {
+    assert_eq!(hsv_to_hex(0.0, 1.0, 1.0), "#ff0000");
+    assert_eq!(hsv_to_hex(1.0 / 3.0, 1.0, 1.0), "#00ff00");
+    assert_eq!(hsv_to_hex(2.0 / 3.0, 1.0, 1.0), "#0000ff");
+    assert_eq!(hsv_to_hex(0.5, 0.0, 1.0), "#ffffff");
+}